@@ -1,23 +1,365 @@
 use crate::lab::config::LabConfig;
-use crate::lab::types::gene_type::GeneTypeTrait;
-use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use crate::lab::config::config_format::ConfigFormat;
+use crate::lab::error::{LabError, SnapshotError};
+use crate::lab::gene_id_trie::GeneIdTrie;
+use crate::lab::regulatory_network::RegulatoryNetwork;
+use crate::lab::snapshot::{GeneTypeRegistry, LabSnapshot, SNAPSHOT_VERSION};
+use crate::lab::types::codon::{Codon, CodonType};
+use crate::lab::types::dominance::Dominance;
+use crate::lab::types::gene::{Gene, GeneLocation};
+use crate::lab::types::gene_type::{GeneType, GeneTypeTrait};
+use crate::lab::types::genome::Genome;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::organism::Organism;
+use crate::lab::types::phenotype::Phenotype;
+use crate::lab::types::rna_sequence::RnaSequence;
+use std::collections::BTreeMap;
+use std::path::Path;
 
+pub mod analytics;
+pub mod archive;
 pub mod config;
+pub mod diff;
+pub mod error;
+pub mod evolution;
+pub mod fm_index;
+pub mod gene_id_trie;
+pub mod motif;
+pub mod mutation;
+pub mod patch;
+pub mod protein;
+pub mod regulatory_network;
+pub mod render;
+pub mod reproduction;
+pub mod session;
+pub mod snapshot;
 pub mod types;
 
+/// Gene type id sequence and node id, alleles share both.
+type AlleleKey = (Vec<CodonType>, u64);
+
 #[derive(Debug)]
 pub struct Lab {
     config: LabConfig,
-    genes: HashMap<u64, Box<dyn GeneTypeTrait>>,
+    genes: BTreeMap<Vec<CodonType>, Box<dyn GeneTypeTrait>>,
+    gene_id_trie: GeneIdTrie,
+    /// Named populations of organisms, stored in snapshots alongside the config and gene types.
+    populations: BTreeMap<String, Vec<Organism>>,
 }
 
 impl Lab {
-    pub fn new(config: LabConfig, genes: HashMap<u64, Box<dyn GeneTypeTrait>>) -> Self {
-        Self { config, genes }
+    pub fn new(config: LabConfig) -> Self {
+        Self {
+            config,
+            genes: BTreeMap::new(),
+            gene_id_trie: GeneIdTrie::new(vec![]),
+            populations: BTreeMap::new(),
+        }
+    }
+
+    pub fn with_builtin_genes(config: LabConfig) -> Self {
+        let mut lab = Self::new(config);
+        for gene_type in GeneType::all() {
+            lab.register_gene(Box::new(gene_type))
+                .expect("Built-in gene type ids must be unambiguous");
+        }
+        lab
+    }
+
+    pub fn get_config(&self) -> &LabConfig {
+        &self.config
+    }
+
+    pub fn get_gene_type(&self, id_sequence: &[CodonType]) -> Option<&dyn GeneTypeTrait> {
+        self.genes
+            .get(id_sequence)
+            .map(|gene_type| gene_type.as_ref())
+    }
+
+    pub fn get_gene_id_trie(&self) -> &GeneIdTrie {
+        &self.gene_id_trie
+    }
+
+    /// Dominance of a gene type, taking overrides from the config into account.
+    pub fn get_dominance(&self, gene_type: &dyn GeneTypeTrait) -> Dominance {
+        self.config
+            .dominance
+            .get(gene_type.name())
+            .copied()
+            .unwrap_or_else(|| gene_type.dominance())
+    }
+
+    pub fn gene_types(&self) -> impl Iterator<Item = &dyn GeneTypeTrait> {
+        self.genes.values().map(|gene_type| gene_type.as_ref())
+    }
+
+    pub fn get_populations(&self) -> &BTreeMap<String, Vec<Organism>> {
+        &self.populations
+    }
+
+    pub fn get_population(&self, name: &str) -> Option<&Vec<Organism>> {
+        self.populations.get(name)
+    }
+
+    pub fn get_population_mut(&mut self, name: &str) -> Option<&mut Vec<Organism>> {
+        self.populations.get_mut(name)
+    }
+
+    /// Adds or replaces a population, returning the replaced one.
+    pub fn set_population(
+        &mut self,
+        name: &str,
+        organisms: Vec<Organism>,
+    ) -> Option<Vec<Organism>> {
+        self.populations.insert(name.to_string(), organisms)
+    }
+
+    pub fn remove_population(&mut self, name: &str) -> Option<Vec<Organism>> {
+        self.populations.remove(name)
+    }
+
+    /// Captures the config, the registered gene types and the populations.
+    ///
+    /// Fails if a gene type doesn't support snapshots.
+    pub fn snapshot(&self) -> Result<LabSnapshot, SnapshotError> {
+        let gene_types = self
+            .gene_types()
+            .map(|gene_type| {
+                gene_type
+                    .snapshot()
+                    .ok_or_else(|| SnapshotError::UnsupportedGeneType(gene_type.name().to_string()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(LabSnapshot {
+            version: SNAPSHOT_VERSION,
+            config: self.config.clone(),
+            gene_types,
+            populations: self.populations.clone(),
+        })
+    }
+
+    /// Restores a lab, gene types are constructed by the registry according to their tags.
+    pub fn from_snapshot(
+        snapshot: LabSnapshot,
+        registry: &GeneTypeRegistry,
+    ) -> Result<Self, SnapshotError> {
+        if snapshot.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(snapshot.version));
+        }
+        snapshot.config.validate()?;
+
+        let mut lab = Self::new(snapshot.config);
+        for gene_type in &snapshot.gene_types {
+            lab.register_gene(registry.restore(gene_type)?)?;
+        }
+        lab.populations = snapshot.populations;
+        Ok(lab)
+    }
+
+    /// Saves a snapshot to a single file, the format is chosen by the file extension like for
+    /// [`LabConfig::save`].
+    pub fn save(&self, path: &Path) -> Result<(), SnapshotError> {
+        let content = ConfigFormat::from_path_or_default(path)
+            .serialize(&self.snapshot()?)
+            .map_err(SnapshotError::Serialize)?;
+        std::fs::write(path, content).map_err(|source| SnapshotError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn load(path: &Path, registry: &GeneTypeRegistry) -> Result<Self, SnapshotError> {
+        let content = std::fs::read_to_string(path).map_err(|source| SnapshotError::Io {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        let value = ConfigFormat::from_path_or_default(path)
+            .parse_value(&content)
+            .map_err(|message| SnapshotError::Parse {
+                origin: path.display().to_string(),
+                message,
+            })?;
+        let snapshot = serde_yaml::from_value(value).map_err(|error| SnapshotError::Parse {
+            origin: path.display().to_string(),
+            message: error.to_string(),
+        })?;
+        Self::from_snapshot(snapshot, registry)
+    }
+
+    /// Registers a gene type under its id sequence.
+    ///
+    /// Fails if the id is already taken or if it is a prefix of a registered id (or the other way
+    /// around), since such genes could not be told apart while reading a codon sequence.
+    pub fn register_gene(&mut self, gene_type: Box<dyn GeneTypeTrait>) -> Result<(), LabError> {
+        let id_sequence = gene_type.id_sequence();
+
+        if self.genes.contains_key(&id_sequence) {
+            return Err(LabError::DuplicateGeneId(id_sequence));
+        }
+
+        if let Some(conflicting_id) = self.genes.keys().find(|registered| {
+            registered.starts_with(&id_sequence) || id_sequence.starts_with(registered)
+        }) {
+            return Err(LabError::AmbiguousGeneId {
+                id: id_sequence,
+                conflicting_id: conflicting_id.clone(),
+            });
+        }
+
+        self.genes.insert(id_sequence, gene_type);
+        self.gene_id_trie = GeneIdTrie::new(
+            self.genes
+                .values()
+                .map(|gene_type| gene_type.clone_boxed())
+                .collect(),
+        );
+        Ok(())
+    }
+
+    /// Splices a sequence according to the splicing config, producing one transcript per
+    /// alternative splicing. Without splicing config (or with invalid motifs) the sequence is
+    /// returned as is.
+    pub fn splice(&self, sequence: &NucleotideSequence) -> Vec<NucleotideSequence> {
+        let Some(splicing) = &self.config.splicing else {
+            return vec![sequence.clone()];
+        };
+        let Ok(splice_sites) = splicing.get_splice_sites() else {
+            return vec![sequence.clone()];
+        };
+
+        splice_sites.alternative_transcripts(sequence, splicing.max_transcripts.max(1))
+    }
+
+    /// Transcribes the regions between promoters and terminators according to the transcription
+    /// config and splices each of them into mature RNA. Without transcription config (or with
+    /// invalid motifs) the whole sequence is transcribed.
+    pub fn transcribe(&self, sequence: &NucleotideSequence) -> Vec<RnaSequence> {
+        let motifs = self
+            .config
+            .transcription
+            .as_ref()
+            .and_then(|transcription| transcription.get_motifs().ok());
+
+        let regions = match motifs {
+            Some(motifs) => motifs
+                .find_transcribed_regions(sequence.get_sequence())
+                .into_iter()
+                .map(|region| NucleotideSequence::new(sequence.get_sequence()[region].to_vec()))
+                .collect(),
+            None => vec![sequence.clone()],
+        };
+
+        regions
+            .iter()
+            .flat_map(|region| self.splice(region))
+            .map(|transcript| RnaSequence::from(&transcript))
+            .collect()
+    }
+
+    /// Reads all genes from a codon sequence.
+    ///
+    /// A gene starts with a `Met` codon directly followed by a registered id sequence, its payload
+    /// runs until the next stop codon. Genes which are never terminated are not read.
+    pub fn read_genes(&self, codons: &[Codon]) -> Vec<Gene> {
+        self.locate_genes(codons)
+            .into_iter()
+            .map(GeneLocation::into_gene)
+            .collect()
+    }
+
+    /// Like [`Self::read_genes`], keeping the codons each gene was read from.
+    pub fn locate_genes(&self, codons: &[Codon]) -> Vec<GeneLocation> {
+        let mut genes = Vec::new();
+        let mut position = 0;
+
+        while position < codons.len() {
+            if codons[position] != Codon::Met {
+                position += 1;
+                continue;
+            }
+
+            let Some((gene_type, id_length)) =
+                self.gene_id_trie.find_prefix(&codons[position + 1..])
+            else {
+                position += 1;
+                continue;
+            };
+
+            let payload_start = position + 1 + id_length;
+            let Some(payload_length) = codons[payload_start..]
+                .iter()
+                .position(|codon| codon.get_type() == CodonType::Stop)
+            else {
+                break;
+            };
+
+            let payload_end = payload_start + payload_length;
+            let gene =
+                gene_type.parse_codon_sequence(&codons[payload_start..payload_end], &self.config);
+            genes.push(GeneLocation::new(gene, position..payload_end + 1));
+            position = payload_end + 1;
+        }
+
+        genes
+    }
+
+    /// Reads all genes, lets the regulatory genes settle into a steady state and builds the
+    /// phenotype from the resulting gene strengths.
+    pub fn express(&self, codons: &[Codon]) -> Phenotype {
+        self.express_genes(self.read_genes(codons))
+    }
+
+    /// Reads the genes of every transcript of every chromosome.
+    pub fn read_genome_genes(&self, genome: &Genome) -> Vec<Gene> {
+        genome
+            .get_chromosomes()
+            .iter()
+            .flat_map(|chromosome| self.transcribe(chromosome.get_sequence()))
+            .flat_map(|transcript| self.read_genes(transcript.translate().get_sequence()))
+            .collect()
+    }
+
+    /// Expresses the genes of all chromosomes, a genome is a single set of chromosomes so there
+    /// are no alleles to resolve.
+    pub fn express_genome(&self, genome: &Genome) -> Phenotype {
+        self.express_genes(self.read_genome_genes(genome))
+    }
+
+    /// Reads the genes of every transcript of every homolog and resolves alleles of the same gene
+    /// type on the same node by their dominance before expressing them. Alternative transcripts
+    /// of one homolog only contribute their strongest gene as that homolog's allele.
+    pub fn express_organism(&self, organism: &Organism) -> Phenotype {
+        let mut alleles: BTreeMap<AlleleKey, BTreeMap<usize, Gene>> = BTreeMap::new();
+        for (homolog_index, homolog) in organism.get_homologs().iter().enumerate() {
+            for transcript in self.transcribe(homolog) {
+                let codons = transcript.translate();
+                for gene in self.read_genes(codons.get_sequence()) {
+                    let key = (gene.get_gene_type().id_sequence(), gene.get_node_id());
+                    let homologs = alleles.entry(key).or_default();
+                    match homologs.get(&homolog_index) {
+                        Some(allele) if allele.get_strength() >= gene.get_strength() => {}
+                        _ => {
+                            homologs.insert(homolog_index, gene);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut genes = Vec::new();
+        for homologs in alleles.into_values() {
+            let homolog_count = homologs.len();
+            let group: Vec<Gene> = homologs.into_values().collect();
+            let dominance = self.get_dominance(group[0].get_gene_type());
+            genes.extend(dominance.resolve(group, homolog_count, organism.get_ploidy()));
+        }
+
+        self.express_genes(genes)
     }
 
-    pub fn register_gene(&mut self, gene_type: Box<dyn GeneTypeTrait>) {
-        self.genes.insert(gene_type.id_sequence_hash(), gene_type);
+    fn express_genes(&self, mut genes: Vec<Gene>) -> Phenotype {
+        RegulatoryNetwork::regulate(&mut genes);
+        Phenotype::from_genes(&genes)
     }
 }
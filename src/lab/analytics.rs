@@ -0,0 +1,297 @@
+use crate::lab::types::codon::{Codon, CodonType};
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::genome::Genome;
+use crate::lab::types::nucleotide::Nucleotide;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+const NUCLEOTIDES: [Nucleotide; 4] = [Nucleotide::A, Nucleotide::T, Nucleotide::G, Nucleotide::C];
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AnalyticsSettings {
+    /// Nucleotides per GC content window, no windows are computed if 0.
+    pub window: usize,
+    pub window_step: usize,
+    pub kmer_size: usize,
+}
+
+impl Default for AnalyticsSettings {
+    fn default() -> Self {
+        Self {
+            window: 100,
+            window_step: 50,
+            kmer_size: 3,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GcWindow {
+    /// Index of the sequence the window belongs to.
+    pub sequence: usize,
+    pub start: usize,
+    pub gc_content: f64,
+}
+
+/// Statistics of one or more nucleotide sequences, e.g. all chromosomes of a population.
+///
+/// Codons are read in the first reading frame of every sequence. Frequencies are relative to the
+/// total of their kind and 0 for empty sequences.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SequenceStats {
+    pub sequences: usize,
+    pub length: usize,
+    pub nucleotide_counts: BTreeMap<String, usize>,
+    pub gc_content: f64,
+    pub gc_windows: Vec<GcWindow>,
+    pub codon_usage: BTreeMap<String, f64>,
+    pub amino_acid_composition: BTreeMap<String, f64>,
+    /// Stop codons per codon in each of the three reading frames.
+    pub stop_codon_density: [f64; 3],
+    /// Shannon entropy of the nucleotides in bits, 2 for uniformly random sequences.
+    pub entropy: f64,
+    pub kmer_size: usize,
+    pub kmer_counts: BTreeMap<String, usize>,
+    /// Shannon entropy of the k-mers in bits.
+    pub kmer_entropy: f64,
+}
+
+impl SequenceStats {
+    pub fn of(nucleotides: &[Nucleotide], settings: &AnalyticsSettings) -> Self {
+        Self::of_sequences([nucleotides], settings)
+    }
+
+    /// Statistics of the nucleotides of the codons, including the remainder.
+    pub fn of_codons(codons: &CodonSequence, settings: &AnalyticsSettings) -> Self {
+        Self::of(&codons.to_nucleotides(), settings)
+    }
+
+    pub fn of_genome(genome: &Genome, settings: &AnalyticsSettings) -> Self {
+        Self::of_population(std::slice::from_ref(genome), settings)
+    }
+
+    /// Statistics over all chromosomes of all genomes, to compare populations across
+    /// generations.
+    pub fn of_population(genomes: &[Genome], settings: &AnalyticsSettings) -> Self {
+        Self::of_sequences(
+            genomes
+                .iter()
+                .flat_map(|genome| genome.get_chromosomes())
+                .map(|chromosome| chromosome.get_sequence().get_sequence()),
+            settings,
+        )
+    }
+
+    pub fn of_sequences<'a>(
+        sequences: impl IntoIterator<Item = &'a [Nucleotide]>,
+        settings: &AnalyticsSettings,
+    ) -> Self {
+        let mut sequence_count = 0;
+        let mut nucleotide_counts = [0usize; 4];
+        let mut gc_windows = Vec::new();
+        let mut codon_counts: BTreeMap<Codon, usize> = BTreeMap::new();
+        let mut stop_counts = [0usize; 3];
+        let mut frame_codons = [0usize; 3];
+        let mut kmer_counts = BTreeMap::new();
+
+        for (index, nucleotides) in sequences.into_iter().enumerate() {
+            sequence_count += 1;
+            for nucleotide in nucleotides {
+                nucleotide_counts[*nucleotide as usize] += 1;
+            }
+            gc_windows.extend(
+                windowed_gc_content(nucleotides, settings.window, settings.window_step)
+                    .into_iter()
+                    .map(|(start, gc_content)| GcWindow {
+                        sequence: index,
+                        start,
+                        gc_content,
+                    }),
+            );
+
+            for frame in 0..3 {
+                let codons =
+                    CodonSequence::from_nucleotides(&nucleotides[frame.min(nucleotides.len())..]);
+                frame_codons[frame] += codons.get_sequence().len();
+                stop_counts[frame] += codons
+                    .get_sequence()
+                    .iter()
+                    .filter(|codon| codon.get_type() == CodonType::Stop)
+                    .count();
+                if frame == 0 {
+                    for codon in codons.get_sequence() {
+                        *codon_counts.entry(*codon).or_default() += 1;
+                    }
+                }
+            }
+
+            for (kmer, count) in kmer_spectrum(nucleotides, settings.kmer_size) {
+                *kmer_counts.entry(kmer).or_default() += count;
+            }
+        }
+
+        let length = nucleotide_counts.iter().sum();
+        let codon_total = frame_codons[0];
+        let mut amino_acid_counts: BTreeMap<CodonType, usize> = BTreeMap::new();
+        for (codon, count) in &codon_counts {
+            *amino_acid_counts.entry(codon.get_type()).or_default() += count;
+        }
+
+        Self {
+            sequences: sequence_count,
+            length,
+            nucleotide_counts: NUCLEOTIDES
+                .iter()
+                .map(|nucleotide| {
+                    (
+                        char::from(*nucleotide).to_string(),
+                        nucleotide_counts[*nucleotide as usize],
+                    )
+                })
+                .collect(),
+            gc_content: ratio(
+                nucleotide_counts[Nucleotide::G as usize]
+                    + nucleotide_counts[Nucleotide::C as usize],
+                length,
+            ),
+            gc_windows,
+            codon_usage: codon_counts
+                .iter()
+                .map(|(codon, count)| (codon_code(codon), ratio(*count, codon_total)))
+                .collect(),
+            amino_acid_composition: amino_acid_counts
+                .iter()
+                .map(|(codon_type, count)| {
+                    (format!("{:?}", codon_type), ratio(*count, codon_total))
+                })
+                .collect(),
+            stop_codon_density: [0, 1, 2]
+                .map(|frame| ratio(stop_counts[frame], frame_codons[frame])),
+            entropy: shannon_entropy(nucleotide_counts),
+            kmer_size: settings.kmer_size,
+            kmer_entropy: shannon_entropy(kmer_counts.values().copied()),
+            kmer_counts,
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("Statistics only contain maps with string keys")
+    }
+
+    /// One `metric,key,value` row per value, so statistics of several populations can be
+    /// concatenated and filtered by metric.
+    pub fn to_csv(&self) -> String {
+        let mut rows = vec!["metric,key,value".to_string()];
+        let mut row = |metric: &str, key: &str, value: String| {
+            rows.push(format!("{},{},{}", metric, key, value));
+        };
+
+        row("sequences", "", self.sequences.to_string());
+        row("length", "", self.length.to_string());
+        for (nucleotide, count) in &self.nucleotide_counts {
+            row("nucleotide_count", nucleotide, count.to_string());
+        }
+        row("gc_content", "", self.gc_content.to_string());
+        for window in &self.gc_windows {
+            row(
+                "gc_window",
+                &format!("{}:{}", window.sequence, window.start),
+                window.gc_content.to_string(),
+            );
+        }
+        for (codon, frequency) in &self.codon_usage {
+            row("codon_usage", codon, frequency.to_string());
+        }
+        for (amino_acid, frequency) in &self.amino_acid_composition {
+            row("amino_acid", amino_acid, frequency.to_string());
+        }
+        for (frame, density) in self.stop_codon_density.iter().enumerate() {
+            row(
+                "stop_codon_density",
+                &frame.to_string(),
+                density.to_string(),
+            );
+        }
+        row("entropy", "", self.entropy.to_string());
+        for (kmer, count) in &self.kmer_counts {
+            row("kmer_count", kmer, count.to_string());
+        }
+        row("kmer_entropy", "", self.kmer_entropy.to_string());
+
+        rows.join("\n") + "\n"
+    }
+}
+
+pub fn gc_content(nucleotides: &[Nucleotide]) -> f64 {
+    let gc = nucleotides
+        .iter()
+        .filter(|nucleotide| matches!(nucleotide, Nucleotide::G | Nucleotide::C))
+        .count();
+    ratio(gc, nucleotides.len())
+}
+
+/// GC content of every complete window as `(start, gc_content)`. Sequences shorter than the
+/// window have a single window covering the whole sequence.
+pub fn windowed_gc_content(
+    nucleotides: &[Nucleotide],
+    window: usize,
+    step: usize,
+) -> Vec<(usize, f64)> {
+    if window == 0 || nucleotides.is_empty() {
+        return Vec::new();
+    }
+    if nucleotides.len() <= window {
+        return vec![(0, gc_content(nucleotides))];
+    }
+
+    (0..=nucleotides.len() - window)
+        .step_by(step.max(1))
+        .map(|start| (start, gc_content(&nucleotides[start..start + window])))
+        .collect()
+}
+
+/// Occurrences of every overlapping k-mer, keyed by its ATGC code.
+pub fn kmer_spectrum(nucleotides: &[Nucleotide], k: usize) -> BTreeMap<String, usize> {
+    let mut spectrum = BTreeMap::new();
+    if k == 0 {
+        return spectrum;
+    }
+    for kmer in nucleotides.windows(k) {
+        let code: String = kmer
+            .iter()
+            .map(|nucleotide| char::from(*nucleotide))
+            .collect();
+        *spectrum.entry(code).or_default() += 1;
+    }
+    spectrum
+}
+
+/// Shannon entropy in bits of the distribution given by the counts.
+pub fn shannon_entropy(counts: impl IntoIterator<Item = usize>) -> f64 {
+    let counts: Vec<usize> = counts.into_iter().filter(|count| *count > 0).collect();
+    let total: usize = counts.iter().sum();
+    counts
+        .iter()
+        .map(|count| {
+            let probability = *count as f64 / total as f64;
+            -probability * probability.log2()
+        })
+        .sum::<f64>()
+        .max(0.0)
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        0.0
+    } else {
+        count as f64 / total as f64
+    }
+}
+
+fn codon_code(codon: &Codon) -> String {
+    codon
+        .to_nucleotides()
+        .iter()
+        .map(|nucleotide| char::from(*nucleotide))
+        .collect()
+}
@@ -0,0 +1,400 @@
+use crate::lab::diff::{SequenceDiff, SequenceEdit};
+use crate::lab::error::ArchiveError;
+use crate::lab::patch::{ChromosomePatch, GenomePatch};
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use flate2::Compression;
+use flate2::read::DeflateDecoder;
+use flate2::write::DeflateEncoder;
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+const ARCHIVE_MAGIC: &[u8; 4] = b"BLPA";
+const ARCHIVE_VERSION: u8 = 1;
+
+const RECORD_RAW: u8 = 0;
+const RECORD_DEFLATE: u8 = 1;
+
+const CHANGE_MODIFY: u8 = 0;
+const CHANGE_ADD: u8 = 1;
+const CHANGE_REMOVE: u8 = 2;
+
+const EDIT_SUBSTITUTION: u8 = 0;
+const EDIT_INSERTION: u8 = 1;
+const EDIT_DELETION: u8 = 2;
+const EDIT_INVERSION: u8 = 3;
+
+/// A population stored as one reference genome and, per member, the patch turning the reference
+/// into the member.
+///
+/// Layout: magic, version (u8), reference length (u32) and the reference genome bytes (see
+/// [`Genome::to_bytes`]), followed by the member records. A record is its length (u32), a flag
+/// byte (raw or deflate) and the binary patch. All integers little endian, the numbers inside a
+/// patch are LEB128 varints. Members are only ever appended, so a file cut off while writing
+/// loses at most the last member.
+#[derive(Debug)]
+pub struct PopulationArchive {
+    file: File,
+    reference: Genome,
+    /// File offset of every member record.
+    offsets: Vec<u64>,
+    end: u64,
+}
+
+impl PopulationArchive {
+    /// Creates a new archive, overwriting an existing file.
+    pub fn create(path: &Path, reference: Genome) -> Result<Self, ArchiveError> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .open(path)?;
+
+        let reference_bytes = reference.to_bytes();
+        let mut header = ARCHIVE_MAGIC.to_vec();
+        header.push(ARCHIVE_VERSION);
+        header.extend_from_slice(&(reference_bytes.len() as u32).to_le_bytes());
+        header.extend_from_slice(&reference_bytes);
+        file.write_all(&header)?;
+
+        Ok(Self {
+            file,
+            reference,
+            offsets: Vec::new(),
+            end: header.len() as u64,
+        })
+    }
+
+    /// Opens an archive for reading and appending. Only the record lengths are read to index the
+    /// members, a truncated last record is dropped.
+    pub fn open(path: &Path) -> Result<Self, ArchiveError> {
+        let file = OpenOptions::new().read(true).write(true).open(path)?;
+        let file_length = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+
+        let mut header = [0u8; 9];
+        reader
+            .read_exact(&mut header)
+            .map_err(|_| invalid("missing archive header"))?;
+        if &header[..4] != ARCHIVE_MAGIC {
+            return Err(invalid("missing archive header"));
+        }
+        if header[4] != ARCHIVE_VERSION {
+            return Err(invalid(&format!(
+                "unsupported archive version {}",
+                header[4]
+            )));
+        }
+
+        let reference_length = u32::from_le_bytes(header[5..9].try_into().unwrap_or_default());
+        let mut reference_bytes = vec![0u8; reference_length as usize];
+        reader
+            .read_exact(&mut reference_bytes)
+            .map_err(|_| invalid("truncated reference genome"))?;
+        let reference = Genome::from_bytes(&reference_bytes)
+            .map_err(|error| invalid(&format!("invalid reference genome: {}", error)))?;
+
+        let mut offsets = Vec::new();
+        let mut position = header.len() as u64 + reference_length as u64;
+        let mut length_bytes = [0u8; 4];
+        while position + 4 <= file_length {
+            reader.read_exact(&mut length_bytes)?;
+            let record_end = position + 4 + u32::from_le_bytes(length_bytes) as u64;
+            if record_end > file_length {
+                break;
+            }
+            offsets.push(position);
+            reader.seek_relative(record_end as i64 - position as i64 - 4)?;
+            position = record_end;
+        }
+
+        Ok(Self {
+            file: reader.into_inner(),
+            reference,
+            offsets,
+            end: position,
+        })
+    }
+
+    pub fn get_reference(&self) -> &Genome {
+        &self.reference
+    }
+
+    pub fn len(&self) -> usize {
+        self.offsets.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.offsets.is_empty()
+    }
+
+    /// Appends a member and returns its index. The record is written with a single write call.
+    pub fn append(&mut self, genome: &Genome) -> Result<usize, ArchiveError> {
+        let patch = GenomePatch::between(&self.reference, genome);
+        let payload = encode_patch(&patch);
+
+        let mut encoder = DeflateEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(&payload)?;
+        let compressed = encoder.finish()?;
+        let (flag, body) = if compressed.len() < payload.len() {
+            (RECORD_DEFLATE, compressed)
+        } else {
+            (RECORD_RAW, payload)
+        };
+
+        let mut record = ((body.len() + 1) as u32).to_le_bytes().to_vec();
+        record.push(flag);
+        record.extend_from_slice(&body);
+
+        // Overwrites a truncated record left behind by an interrupted append
+        self.file.seek(SeekFrom::Start(self.end))?;
+        self.file.write_all(&record)?;
+        self.file.set_len(self.end + record.len() as u64)?;
+
+        self.offsets.push(self.end);
+        self.end += record.len() as u64;
+        Ok(self.offsets.len() - 1)
+    }
+
+    pub fn get_patch(&mut self, index: usize) -> Result<GenomePatch, ArchiveError> {
+        let offset = *self.offsets.get(index).ok_or(ArchiveError::OutOfRange {
+            index,
+            length: self.offsets.len(),
+        })?;
+
+        self.file.seek(SeekFrom::Start(offset))?;
+        let mut length_bytes = [0u8; 4];
+        self.file.read_exact(&mut length_bytes)?;
+        let mut record = vec![0u8; u32::from_le_bytes(length_bytes) as usize];
+        self.file.read_exact(&mut record)?;
+
+        let payload = match record.split_first() {
+            Some((&RECORD_RAW, body)) => body.to_vec(),
+            Some((&RECORD_DEFLATE, body)) => {
+                let mut payload = Vec::new();
+                DeflateDecoder::new(body).read_to_end(&mut payload)?;
+                payload
+            }
+            _ => return Err(invalid("unknown record encoding")),
+        };
+        decode_patch(&payload)
+    }
+
+    pub fn get(&mut self, index: usize) -> Result<Genome, ArchiveError> {
+        Ok(self.get_patch(index)?.apply(&self.reference)?)
+    }
+
+    /// Reads the members in order, one at a time.
+    pub fn iter(&mut self) -> impl Iterator<Item = Result<Genome, ArchiveError>> + '_ {
+        (0..self.len()).map(|index| self.get(index))
+    }
+}
+
+fn invalid(reason: &str) -> ArchiveError {
+    ArchiveError::InvalidData(reason.to_string())
+}
+
+fn encode_patch(patch: &GenomePatch) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    write_varint(&mut bytes, patch.get_changes().len() as u64);
+
+    for change in patch.get_changes() {
+        match change {
+            ChromosomePatch::Modify { name, diff } => {
+                bytes.push(CHANGE_MODIFY);
+                write_name(&mut bytes, name);
+                write_edits(&mut bytes, diff.get_edits());
+            }
+            ChromosomePatch::Add { index, chromosome } => {
+                bytes.push(CHANGE_ADD);
+                write_name(&mut bytes, chromosome.get_name());
+                write_varint(&mut bytes, *index as u64);
+                write_nucleotides(&mut bytes, chromosome.get_sequence().get_sequence());
+            }
+            ChromosomePatch::Remove(name) => {
+                bytes.push(CHANGE_REMOVE);
+                write_name(&mut bytes, name);
+            }
+        }
+    }
+    bytes
+}
+
+/// Positions are stored relative to the end of the previous edit.
+fn write_edits(bytes: &mut Vec<u8>, edits: &[SequenceEdit]) {
+    write_varint(bytes, edits.len() as u64);
+    let mut previous_end = 0;
+
+    for edit in edits {
+        let position = edit.get_position();
+        match edit {
+            SequenceEdit::Substitution { from, to, .. } => {
+                bytes.push(EDIT_SUBSTITUTION);
+                write_varint(bytes, (position - previous_end) as u64);
+                bytes.push(((*from as u8) << 2) | *to as u8);
+            }
+            SequenceEdit::Insertion { nucleotides, .. } => {
+                bytes.push(EDIT_INSERTION);
+                write_varint(bytes, (position - previous_end) as u64);
+                write_nucleotides(bytes, nucleotides);
+            }
+            SequenceEdit::Deletion { nucleotides, .. } => {
+                bytes.push(EDIT_DELETION);
+                write_varint(bytes, (position - previous_end) as u64);
+                write_nucleotides(bytes, nucleotides);
+            }
+            SequenceEdit::Inversion { length, .. } => {
+                bytes.push(EDIT_INVERSION);
+                write_varint(bytes, (position - previous_end) as u64);
+                write_varint(bytes, *length as u64);
+            }
+        }
+        previous_end = position + edit.get_original_length();
+    }
+}
+
+fn write_varint(bytes: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        bytes.push((value as u8) | 0x80);
+        value >>= 7;
+    }
+    bytes.push(value as u8);
+}
+
+fn write_name(bytes: &mut Vec<u8>, name: &str) {
+    write_varint(bytes, name.len() as u64);
+    bytes.extend_from_slice(name.as_bytes());
+}
+
+fn write_nucleotides(bytes: &mut Vec<u8>, nucleotides: &[Nucleotide]) {
+    let packed = NucleotideSequence::new(nucleotides.to_vec()).to_bytes();
+    write_varint(bytes, packed.len() as u64);
+    bytes.extend_from_slice(&packed);
+}
+
+fn decode_patch(bytes: &[u8]) -> Result<GenomePatch, ArchiveError> {
+    let mut reader = PatchReader { bytes, position: 0 };
+    let change_count = reader.varint()?;
+    let mut changes = Vec::new();
+
+    for _ in 0..change_count {
+        let change = match reader.byte()? {
+            CHANGE_MODIFY => ChromosomePatch::Modify {
+                name: reader.name()?,
+                diff: reader.edits()?,
+            },
+            CHANGE_ADD => {
+                let name = reader.name()?;
+                let index = reader.varint()? as usize;
+                let nucleotides = reader.nucleotides()?;
+                ChromosomePatch::Add {
+                    index,
+                    chromosome: Chromosome::new(name, NucleotideSequence::new(nucleotides)),
+                }
+            }
+            CHANGE_REMOVE => ChromosomePatch::Remove(reader.name()?),
+            tag => return Err(invalid(&format!("unknown change tag {}", tag))),
+        };
+        changes.push(change);
+    }
+    Ok(GenomePatch::new(changes))
+}
+
+struct PatchReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> PatchReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], ArchiveError> {
+        let slice = self
+            .bytes
+            .get(self.position..self.position + length)
+            .ok_or_else(|| invalid("unexpected end of record"))?;
+        self.position += length;
+        Ok(slice)
+    }
+
+    fn byte(&mut self) -> Result<u8, ArchiveError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn varint(&mut self) -> Result<u64, ArchiveError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.byte()?;
+            value |= ((byte & 0x7f) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(invalid("varint too long"))
+    }
+
+    fn length(&mut self) -> Result<usize, ArchiveError> {
+        let value = self.varint()? as usize;
+        if value > self.bytes.len() * 4 {
+            return Err(invalid("length exceeds the record"));
+        }
+        Ok(value)
+    }
+
+    fn name(&mut self) -> Result<String, ArchiveError> {
+        let length = self.length()?;
+        String::from_utf8(self.take(length)?.to_vec())
+            .map_err(|_| invalid("chromosome name is not valid UTF-8"))
+    }
+
+    fn nucleotides(&mut self) -> Result<Vec<Nucleotide>, ArchiveError> {
+        let length = self.length()?;
+        let packed = self.take(length)?;
+        NucleotideSequence::try_from_bytes(packed)
+            .map(|sequence| sequence.get_sequence().to_vec())
+            .map_err(|_| invalid("invalid packed nucleotides"))
+    }
+
+    fn edits(&mut self) -> Result<SequenceDiff, ArchiveError> {
+        let count = self.length()?;
+        let mut edits = Vec::with_capacity(count);
+        let mut previous_end = 0;
+
+        for _ in 0..count {
+            let tag = self.byte()?;
+            let position = usize::try_from(self.varint()?)
+                .ok()
+                .and_then(|offset| offset.checked_add(previous_end))
+                .ok_or_else(|| invalid("edit position out of range"))?;
+            let edit = match tag {
+                EDIT_SUBSTITUTION => {
+                    let byte = self.byte()?;
+                    SequenceEdit::Substitution {
+                        position,
+                        from: Nucleotide::from(byte >> 2),
+                        to: Nucleotide::from(byte),
+                    }
+                }
+                EDIT_INSERTION => SequenceEdit::Insertion {
+                    position,
+                    nucleotides: self.nucleotides()?,
+                },
+                EDIT_DELETION => SequenceEdit::Deletion {
+                    position,
+                    nucleotides: self.nucleotides()?,
+                },
+                EDIT_INVERSION => SequenceEdit::Inversion {
+                    position,
+                    length: self.varint()? as usize,
+                },
+                tag => return Err(invalid(&format!("unknown edit tag {}", tag))),
+            };
+            previous_end = position
+                .checked_add(edit.get_original_length())
+                .ok_or_else(|| invalid("edit length out of range"))?;
+            edits.push(edit);
+        }
+        Ok(SequenceDiff::new(edits))
+    }
+}
@@ -1,17 +1,287 @@
 use crate::lab::config::codon_value_config::CodonValueConfig;
-use serde::{Deserialize, Serialize};
+use crate::lab::config::codon_value_table::CodonValueTable;
+use crate::lab::config::config_format::ConfigFormat;
+use crate::lab::config::protein_config::ProteinConfig;
+use crate::lab::config::splicing_config::{MAX_TRANSCRIPTS, SplicingConfig};
+use crate::lab::config::transcription_config::TranscriptionConfig;
+use crate::lab::error::ConfigError;
+use crate::lab::types::dominance::Dominance;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
-mod codon_value_config;
+pub mod codon_value_config;
+pub mod codon_value_table;
+pub mod config_format;
+mod layering;
+pub mod protein_config;
+pub mod splicing_config;
+pub mod transcription_config;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+/// Prefix of environment variables overriding config values, see [`LabConfig::load_layers_with_env`].
+pub const ENV_PREFIX: &str = "BIO_LAB";
+
+/// Every field is optional, missing values are taken from [`LabConfig::default`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
 pub struct LabConfig {
     pub codon_strength: CodonValueConfig<f64>,
+    /// Additional named tables, gene types look them up by name to map codons to their own values.
+    pub codon_tables: BTreeMap<String, CodonValueTable>,
+    /// Overrides the dominance of gene types, keyed by gene type name.
+    pub dominance: BTreeMap<String, Dominance>,
+    pub protein: ProteinConfig,
+    /// Introns are only spliced out if this is set.
+    pub splicing: Option<SplicingConfig>,
+    /// Without transcription config every sequence is transcribed as a whole.
+    pub transcription: Option<TranscriptionConfig>,
+}
+
+impl Default for LabConfig {
+    fn default() -> Self {
+        Self {
+            codon_strength: CodonValueConfig::uniform(1.0),
+            codon_tables: BTreeMap::new(),
+            dominance: BTreeMap::new(),
+            protein: ProteinConfig::default(),
+            splicing: None,
+            transcription: None,
+        }
+    }
 }
 
 impl LabConfig {
-    pub fn load(path: &Path) -> Result<Self, serde_yaml::Error> {
-        let f = std::fs::File::open(path).expect("Failed to open config file");
-        serde_yaml::from_reader(f)
+    pub fn get_codon_table(&self, name: &str) -> Option<&CodonValueTable> {
+        self.codon_tables.get(name)
+    }
+
+    pub fn get_f64_table(&self, name: &str) -> Option<&CodonValueConfig<f64>> {
+        self.get_codon_table(name)?.as_f64()
+    }
+
+    pub fn get_i64_table(&self, name: &str) -> Option<&CodonValueConfig<i64>> {
+        self.get_codon_table(name)?.as_i64()
+    }
+
+    pub fn get_bool_table(&self, name: &str) -> Option<&CodonValueConfig<bool>> {
+        self.get_codon_table(name)?.as_bool()
+    }
+
+    /// Loads a (partial) config file, missing values are taken from the defaults. The format is
+    /// chosen by the file extension, unknown extensions are read as YAML.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        Self::load_layers(&[path])
+    }
+
+    pub fn load_with_format(path: &Path, format: ConfigFormat) -> Result<Self, ConfigError> {
+        let layer = read_layer(path, format)?;
+        Self::from_layers(vec![layer], ENV_PREFIX, [])
+    }
+
+    /// Parses a (partial) config from a string, e.g. a section embedded in another config file.
+    pub fn from_content(content: &str, format: ConfigFormat) -> Result<Self, ConfigError> {
+        let layer = format
+            .parse_value(content)
+            .map_err(|message| ConfigError::Parse {
+                origin: format!("{} content", format.get_extension()),
+                message,
+            })?;
+        Self::from_layers(vec![layer], ENV_PREFIX, [])
+    }
+
+    /// Deserializes a partial config embedded in another config, use it with
+    /// `#[serde(deserialize_with = "LabConfig::deserialize_layer")]`. Unlike the derived
+    /// implementation, missing table entries are taken from the defaults as well.
+    pub fn deserialize_layer<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let layer = serde_yaml::Value::deserialize(deserializer)?;
+        Self::from_layers(vec![layer], ENV_PREFIX, []).map_err(serde::de::Error::custom)
+    }
+
+    /// Loads the files on top of each other, later files override values of earlier ones.
+    pub fn load_layers(paths: &[&Path]) -> Result<Self, ConfigError> {
+        Self::load_layers_with_vars(paths, ENV_PREFIX, [])
+    }
+
+    /// Like [`Self::load_layers`], but environment variables override the loaded values. A variable
+    /// like `BIO_LAB__CODON_STRENGTH__ALA=2.0` sets `codon_strength.ala` to 2.0.
+    pub fn load_layers_with_env(paths: &[&Path]) -> Result<Self, ConfigError> {
+        Self::load_layers_with_vars(paths, ENV_PREFIX, std::env::vars())
+    }
+
+    pub fn load_layers_with_vars(
+        paths: &[&Path],
+        env_prefix: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let layers = paths
+            .iter()
+            .map(|path| read_layer(path, ConfigFormat::from_path_or_default(path)))
+            .collect::<Result<Vec<_>, _>>()?;
+        Self::from_layers(layers, env_prefix, vars)
+    }
+
+    fn from_layers(
+        layers: Vec<serde_yaml::Value>,
+        env_prefix: &str,
+        vars: impl IntoIterator<Item = (String, String)>,
+    ) -> Result<Self, ConfigError> {
+        let mut value =
+            serde_yaml::to_value(Self::default()).map_err(|error| ConfigError::Parse {
+                origin: "defaults".to_string(),
+                message: error.to_string(),
+            })?;
+
+        for layer in layers {
+            layering::merge_values(&mut value, layer);
+        }
+
+        layering::apply_env_overrides(&mut value, env_prefix, vars)?;
+        Self::validate_schema(&value)?;
+
+        let config: Self = serde_yaml::from_value(value).map_err(|error| ConfigError::Parse {
+            origin: "merged config".to_string(),
+            message: error.to_string(),
+        })?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Saves the complete config, the format is chosen by the file extension like in [`Self::load`].
+    pub fn save(&self, path: &Path) -> Result<(), ConfigError> {
+        self.save_with_format(path, ConfigFormat::from_path_or_default(path))
+    }
+
+    pub fn save_with_format(&self, path: &Path, format: ConfigFormat) -> Result<(), ConfigError> {
+        let content = self.to_content(format)?;
+        std::fs::write(path, content).map_err(|source| ConfigError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    }
+
+    pub fn to_content(&self, format: ConfigFormat) -> Result<String, ConfigError> {
+        format
+            .serialize(self)
+            .map_err(|message| ConfigError::Serialize { format, message })
+    }
+
+    /// JSON schema generated from the config types.
+    pub fn json_schema() -> serde_json::Value {
+        serde_json::to_value(schemars::schema_for!(LabConfig))
+            .expect("Generated schema must be serializable")
+    }
+
+    /// Checks raw config values against the JSON schema, problems are reported with the JSON
+    /// pointer of the offending value.
+    pub fn validate_schema(value: &serde_yaml::Value) -> Result<(), ConfigError> {
+        let instance = serde_json::to_value(value).map_err(|error| ConfigError::Parse {
+            origin: "merged config".to_string(),
+            message: error.to_string(),
+        })?;
+        let validator = jsonschema::validator_for(&Self::json_schema())
+            .expect("Generated schema must be a valid JSON schema");
+
+        let problems: Vec<String> = validator
+            .iter_errors(&instance)
+            .map(|error| format!("at '{}': {}", error.instance_path(), error))
+            .collect();
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Validation(problems))
+        }
+    }
+
+    /// Collects every problem instead of stopping at the first one.
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        let mut problems = Vec::new();
+
+        let f64_tables = [
+            ("codon_strength", &self.codon_strength),
+            ("protein.hydrophobicity", &self.protein.hydrophobicity),
+            ("protein.charge", &self.protein.charge),
+            ("protein.mass", &self.protein.mass),
+            ("protein.helix_propensity", &self.protein.helix_propensity),
+            ("protein.sheet_propensity", &self.protein.sheet_propensity),
+        ];
+        for (path, table) in f64_tables {
+            check_finite(path, table, &mut problems);
+            check_variants(path, table, &mut problems);
+        }
+
+        for (name, table) in &self.codon_tables {
+            let path = format!("codon_tables.{}.values", name);
+            match table {
+                CodonValueTable::F64(table) => {
+                    check_finite(&path, table, &mut problems);
+                    check_variants(&path, table, &mut problems);
+                }
+                CodonValueTable::I64(table) => check_variants(&path, table, &mut problems),
+                CodonValueTable::Bool(table) => check_variants(&path, table, &mut problems),
+            }
+        }
+
+        if let Some(splicing) = &self.splicing
+            && let Err(invalid) = splicing.get_splice_sites()
+        {
+            problems.push(format!(
+                "splicing motifs contain '{}', which is not a nucleotide",
+                invalid
+            ));
+        }
+        if let Some(splicing) = &self.splicing
+            && splicing.max_transcripts > MAX_TRANSCRIPTS
+        {
+            problems.push(format!(
+                "splicing.max_transcripts must be at most {}",
+                MAX_TRANSCRIPTS
+            ));
+        }
+        if let Some(transcription) = &self.transcription
+            && let Err(invalid) = transcription.get_motifs()
+        {
+            problems.push(format!(
+                "transcription motifs contain '{}', which is not a nucleotide",
+                invalid
+            ));
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(ConfigError::Validation(problems))
+        }
+    }
+}
+
+fn read_layer(path: &Path, format: ConfigFormat) -> Result<serde_yaml::Value, ConfigError> {
+    let content = std::fs::read_to_string(path).map_err(|source| ConfigError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    format
+        .parse_value(&content)
+        .map_err(|message| ConfigError::Parse {
+            origin: path.display().to_string(),
+            message,
+        })
+}
+
+fn check_finite(path: &str, table: &CodonValueConfig<f64>, problems: &mut Vec<String>) {
+    for (key, value) in table.values() {
+        if !value.is_finite() {
+            problems.push(format!("{}.{} must be a finite number", path, key));
+        }
+    }
+}
+
+fn check_variants<V: Copy>(path: &str, table: &CodonValueConfig<V>, problems: &mut Vec<String>) {
+    for variant in table.invalid_variants() {
+        problems.push(format!(
+            "{}.variants.{} is not a codon of that amino acid",
+            path, variant
+        ));
     }
 }
@@ -1,7 +1,11 @@
-use crate::lab::types::codon::Codon;
-use serde::{Deserialize, Serialize};
+use crate::lab::types::codon::{Codon, CodonType};
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+#[schemars(rename = "CodonValueConfig_for_{V}")]
 pub struct CodonValueConfig<V: Copy> {
     pub met: V,
     pub stop: V,
@@ -24,32 +28,194 @@ pub struct CodonValueConfig<V: Copy> {
     pub trp: V,
     pub tyr: V,
     pub val: V,
+    /// Overrides for single synonymous codons, keyed by amino acid and then by the codon's
+    /// nucleotides, e.g. `leu: { CTG: 2.0 }`. Codons are uppercased while loading, as environment
+    /// overrides arrive in lowercase.
+    #[serde(
+        default,
+        skip_serializing_if = "BTreeMap::is_empty",
+        deserialize_with = "deserialize_variants"
+    )]
+    pub variants: BTreeMap<String, BTreeMap<String, V>>,
 }
 
 impl<V: Copy> CodonValueConfig<V> {
+    pub fn uniform(value: V) -> Self {
+        Self {
+            met: value,
+            stop: value,
+            ala: value,
+            arg: value,
+            asn: value,
+            asp: value,
+            cys: value,
+            gln: value,
+            glu: value,
+            gly: value,
+            his: value,
+            ile: value,
+            leu: value,
+            lys: value,
+            phe: value,
+            pro: value,
+            ser: value,
+            thr: value,
+            trp: value,
+            tyr: value,
+            val: value,
+            variants: BTreeMap::new(),
+        }
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = (String, V)> + '_ {
+        let type_values = [
+            CodonType::Met,
+            CodonType::Stop,
+            CodonType::Ala,
+            CodonType::Arg,
+            CodonType::Asn,
+            CodonType::Asp,
+            CodonType::Cys,
+            CodonType::Gln,
+            CodonType::Glu,
+            CodonType::Gly,
+            CodonType::His,
+            CodonType::Ile,
+            CodonType::Leu,
+            CodonType::Lys,
+            CodonType::Phe,
+            CodonType::Pro,
+            CodonType::Ser,
+            CodonType::Thr,
+            CodonType::Trp,
+            CodonType::Tyr,
+            CodonType::Val,
+        ]
+        .into_iter()
+        .map(|codon_type| {
+            (
+                type_key(codon_type).to_string(),
+                self.get_type_field(codon_type),
+            )
+        });
+
+        let variant_values = self.variants.iter().flat_map(|(amino_acid, variants)| {
+            variants
+                .iter()
+                .map(move |(code, value)| (format!("variants.{}.{}", amino_acid, code), *value))
+        });
+
+        type_values.chain(variant_values)
+    }
+
     pub fn get_value(&self, codon: Codon) -> V {
-        match codon {
-            Codon::Met => self.met,
-            Codon::Stop(_) => self.stop,
-            Codon::Ala(_) => self.ala,
-            Codon::Arg(_) => self.arg,
-            Codon::Asn(_) => self.asn,
-            Codon::Asp(_) => self.asp,
-            Codon::Cys(_) => self.cys,
-            Codon::Gln(_) => self.gln,
-            Codon::Glu(_) => self.glu,
-            Codon::Gly(_) => self.gly,
-            Codon::His(_) => self.his,
-            Codon::Ile(_) => self.ile,
-            Codon::Leu(_) => self.leu,
-            Codon::Lys(_) => self.lys,
-            Codon::Phe(_) => self.phe,
-            Codon::Pro(_) => self.pro,
-            Codon::Ser(_) => self.ser,
-            Codon::Thr(_) => self.thr,
-            Codon::Trp => self.trp,
-            Codon::Tyr(_) => self.tyr,
-            Codon::Val(_) => self.val,
+        self.get_variant_value(codon)
+            .unwrap_or_else(|| self.get_type_value(codon))
+    }
+
+    pub fn get_variant_value(&self, codon: Codon) -> Option<V> {
+        if self.variants.is_empty() {
+            return None;
         }
+
+        let variants = self.variants.get(type_key(codon.get_type()))?;
+        let nucleotides = codon.to_nucleotides().map(u8::from);
+        let code = std::str::from_utf8(&nucleotides).ok()?;
+        variants.get(code).copied()
+    }
+
+    /// Lists overrides which do not belong to any codon, as `amino_acid.CODE`.
+    pub fn invalid_variants(&self) -> Vec<String> {
+        let mut invalid = Vec::new();
+        for (amino_acid, variants) in &self.variants {
+            for code in variants.keys() {
+                let is_valid = code.len() == 3
+                    && code
+                        .chars()
+                        .all(|c| c.is_ascii_uppercase() && "ATGC".contains(c))
+                    && Codon::from_code_string(code)
+                        .is_some_and(|codon| type_key(codon.get_type()) == amino_acid);
+                if !is_valid {
+                    invalid.push(format!("{}.{}", amino_acid, code));
+                }
+            }
+        }
+        invalid
+    }
+
+    pub fn get_type_value(&self, codon: Codon) -> V {
+        self.get_type_field(codon.get_type())
+    }
+
+    fn get_type_field(&self, codon_type: CodonType) -> V {
+        match codon_type {
+            CodonType::Met => self.met,
+            CodonType::Stop => self.stop,
+            CodonType::Ala => self.ala,
+            CodonType::Arg => self.arg,
+            CodonType::Asn => self.asn,
+            CodonType::Asp => self.asp,
+            CodonType::Cys => self.cys,
+            CodonType::Gln => self.gln,
+            CodonType::Glu => self.glu,
+            CodonType::Gly => self.gly,
+            CodonType::His => self.his,
+            CodonType::Ile => self.ile,
+            CodonType::Leu => self.leu,
+            CodonType::Lys => self.lys,
+            CodonType::Phe => self.phe,
+            CodonType::Pro => self.pro,
+            CodonType::Ser => self.ser,
+            CodonType::Thr => self.thr,
+            CodonType::Trp => self.trp,
+            CodonType::Tyr => self.tyr,
+            CodonType::Val => self.val,
+        }
+    }
+}
+
+fn deserialize_variants<'de, D, V>(
+    deserializer: D,
+) -> Result<BTreeMap<String, BTreeMap<String, V>>, D::Error>
+where
+    D: Deserializer<'de>,
+    V: Deserialize<'de>,
+{
+    let variants = BTreeMap::<String, BTreeMap<String, V>>::deserialize(deserializer)?;
+    Ok(variants
+        .into_iter()
+        .map(|(amino_acid, codons)| {
+            let codons = codons
+                .into_iter()
+                .map(|(code, value)| (code.to_uppercase(), value))
+                .collect();
+            (amino_acid, codons)
+        })
+        .collect())
+}
+
+fn type_key(codon_type: CodonType) -> &'static str {
+    match codon_type {
+        CodonType::Met => "met",
+        CodonType::Stop => "stop",
+        CodonType::Ala => "ala",
+        CodonType::Arg => "arg",
+        CodonType::Asn => "asn",
+        CodonType::Asp => "asp",
+        CodonType::Cys => "cys",
+        CodonType::Gln => "gln",
+        CodonType::Glu => "glu",
+        CodonType::Gly => "gly",
+        CodonType::His => "his",
+        CodonType::Ile => "ile",
+        CodonType::Leu => "leu",
+        CodonType::Lys => "lys",
+        CodonType::Phe => "phe",
+        CodonType::Pro => "pro",
+        CodonType::Ser => "ser",
+        CodonType::Thr => "thr",
+        CodonType::Trp => "trp",
+        CodonType::Tyr => "tyr",
+        CodonType::Val => "val",
     }
 }
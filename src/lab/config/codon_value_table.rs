@@ -0,0 +1,61 @@
+use crate::lab::config::codon_value_config::CodonValueConfig;
+use crate::lab::types::codon::Codon;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// A codon value table of one of the supported value types.
+///
+/// Serialized with the value type as tag, e.g. `{ type: f64, values: { met: 1.0, ... } }`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(tag = "type", content = "values", rename_all = "snake_case")]
+pub enum CodonValueTable {
+    F64(CodonValueConfig<f64>),
+    I64(CodonValueConfig<i64>),
+    Bool(CodonValueConfig<bool>),
+}
+
+impl CodonValueTable {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            CodonValueTable::F64(_) => "f64",
+            CodonValueTable::I64(_) => "i64",
+            CodonValueTable::Bool(_) => "bool",
+        }
+    }
+
+    pub fn as_f64(&self) -> Option<&CodonValueConfig<f64>> {
+        match self {
+            CodonValueTable::F64(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    pub fn as_i64(&self) -> Option<&CodonValueConfig<i64>> {
+        match self {
+            CodonValueTable::I64(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    pub fn as_bool(&self) -> Option<&CodonValueConfig<bool>> {
+        match self {
+            CodonValueTable::Bool(table) => Some(table),
+            _ => None,
+        }
+    }
+
+    /// Value of the codon converted to a float, booleans become 0.0 or 1.0.
+    pub fn get_value_f64(&self, codon: Codon) -> f64 {
+        match self {
+            CodonValueTable::F64(table) => table.get_value(codon),
+            CodonValueTable::I64(table) => table.get_value(codon) as f64,
+            CodonValueTable::Bool(table) => {
+                if table.get_value(codon) {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
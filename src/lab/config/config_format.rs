@@ -0,0 +1,68 @@
+use std::path::Path;
+
+/// File format of a config, usually derived from the file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Yaml,
+    Toml,
+    Json,
+}
+
+impl ConfigFormat {
+    pub fn all() -> [ConfigFormat; 3] {
+        [ConfigFormat::Yaml, ConfigFormat::Toml, ConfigFormat::Json]
+    }
+
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "yaml" | "yml" => Some(ConfigFormat::Yaml),
+            "toml" => Some(ConfigFormat::Toml),
+            "json" => Some(ConfigFormat::Json),
+            _ => None,
+        }
+    }
+
+    pub fn from_path(path: &Path) -> Option<Self> {
+        Self::from_extension(path.extension()?.to_str()?)
+    }
+
+    /// Files without a known extension are treated as YAML.
+    pub fn from_path_or_default(path: &Path) -> Self {
+        Self::from_path(path).unwrap_or(ConfigFormat::Yaml)
+    }
+
+    pub fn get_extension(&self) -> &'static str {
+        match self {
+            ConfigFormat::Yaml => "yaml",
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Json => "json",
+        }
+    }
+
+    /// Parses the content into a generic value, the common representation used for layering.
+    pub(crate) fn parse_value(&self, content: &str) -> Result<serde_yaml::Value, String> {
+        match self {
+            ConfigFormat::Yaml => serde_yaml::from_str(content).map_err(|error| error.to_string()),
+            ConfigFormat::Toml => {
+                let value: toml::Value =
+                    toml::from_str(content).map_err(|error| error.to_string())?;
+                serde_yaml::to_value(value).map_err(|error| error.to_string())
+            }
+            ConfigFormat::Json => {
+                let value: serde_json::Value =
+                    serde_json::from_str(content).map_err(|error| error.to_string())?;
+                serde_yaml::to_value(value).map_err(|error| error.to_string())
+            }
+        }
+    }
+
+    pub(crate) fn serialize<T: serde::Serialize>(&self, value: &T) -> Result<String, String> {
+        match self {
+            ConfigFormat::Yaml => serde_yaml::to_string(value).map_err(|error| error.to_string()),
+            ConfigFormat::Toml => toml::to_string_pretty(value).map_err(|error| error.to_string()),
+            ConfigFormat::Json => {
+                serde_json::to_string_pretty(value).map_err(|error| error.to_string())
+            }
+        }
+    }
+}
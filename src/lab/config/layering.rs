@@ -0,0 +1,81 @@
+use crate::lab::error::ConfigError;
+use serde_yaml::{Mapping, Value};
+
+/// Separates the path segments of environment variable names, e.g. `BIO_LAB__CODON_STRENGTH__ALA`.
+const ENV_PATH_SEPARATOR: &str = "__";
+
+/// Merges the layer into the base, mappings are merged key by key and everything else is replaced.
+pub fn merge_values(base: &mut Value, layer: Value) {
+    match (base, layer) {
+        (Value::Mapping(base), Value::Mapping(layer)) => {
+            for (key, value) in layer {
+                match base.get_mut(&key) {
+                    Some(existing) => merge_values(existing, value),
+                    None => {
+                        base.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, layer) => *base = layer,
+    }
+}
+
+/// Applies every variable starting with the prefix followed by the path separator. The remaining
+/// name is split into lowercase path segments and the value is parsed as YAML scalar, so numbers
+/// and booleans keep their type. Map keys which are not lowercase have to be normalized by the
+/// config that reads them.
+pub fn apply_env_overrides(
+    value: &mut Value,
+    prefix: &str,
+    vars: impl IntoIterator<Item = (String, String)>,
+) -> Result<(), ConfigError> {
+    let prefix = format!("{}{}", prefix, ENV_PATH_SEPARATOR);
+
+    for (name, raw_value) in vars {
+        let Some(path) = name.strip_prefix(&prefix) else {
+            continue;
+        };
+
+        let segments: Vec<String> = path
+            .split(ENV_PATH_SEPARATOR)
+            .map(|segment| segment.to_lowercase())
+            .collect();
+        if segments.iter().any(|segment| segment.is_empty()) {
+            return Err(ConfigError::Parse {
+                origin: format!("environment variable {}", name),
+                message: "empty path segment".to_string(),
+            });
+        }
+
+        let parsed = serde_yaml::from_str(&raw_value).map_err(|error| ConfigError::Parse {
+            origin: format!("environment variable {}", name),
+            message: error.to_string(),
+        })?;
+        set_path(value, &segments, parsed);
+    }
+
+    Ok(())
+}
+
+fn set_path(value: &mut Value, segments: &[String], new_value: Value) {
+    let Some((first, rest)) = segments.split_first() else {
+        *value = new_value;
+        return;
+    };
+
+    if !value.is_mapping() {
+        *value = Value::Mapping(Mapping::new());
+    }
+    let Value::Mapping(mapping) = value else {
+        unreachable!()
+    };
+
+    let key = Value::String(first.clone());
+    if !mapping.contains_key(&key) {
+        mapping.insert(key.clone(), Value::Null);
+    }
+    if let Some(child) = mapping.get_mut(&key) {
+        set_path(child, rest, new_value);
+    }
+}
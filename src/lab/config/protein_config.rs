@@ -0,0 +1,147 @@
+use crate::lab::config::codon_value_config::CodonValueConfig;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Per amino acid tables used to derive physical properties of translated proteins.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(default, deny_unknown_fields)]
+pub struct ProteinConfig {
+    /// Kyte-Doolittle hydropathy
+    pub hydrophobicity: CodonValueConfig<f64>,
+    /// Side chain charge at neutral pH
+    pub charge: CodonValueConfig<f64>,
+    /// Average residue mass in Dalton
+    pub mass: CodonValueConfig<f64>,
+    /// Chou-Fasman alpha helix propensity
+    pub helix_propensity: CodonValueConfig<f64>,
+    /// Chou-Fasman beta sheet propensity
+    pub sheet_propensity: CodonValueConfig<f64>,
+}
+
+impl Default for ProteinConfig {
+    fn default() -> Self {
+        Self {
+            hydrophobicity: CodonValueConfig {
+                met: 1.9,
+                stop: 0.0,
+                ala: 1.8,
+                arg: -4.5,
+                asn: -3.5,
+                asp: -3.5,
+                cys: 2.5,
+                gln: -3.5,
+                glu: -3.5,
+                gly: -0.4,
+                his: -3.2,
+                ile: 4.5,
+                leu: 3.8,
+                lys: -3.9,
+                phe: 2.8,
+                pro: -1.6,
+                ser: -0.8,
+                thr: -0.7,
+                trp: -0.9,
+                tyr: -1.3,
+                val: 4.2,
+                variants: BTreeMap::new(),
+            },
+            charge: CodonValueConfig {
+                met: 0.0,
+                stop: 0.0,
+                ala: 0.0,
+                arg: 1.0,
+                asn: 0.0,
+                asp: -1.0,
+                cys: 0.0,
+                gln: 0.0,
+                glu: -1.0,
+                gly: 0.0,
+                his: 0.1,
+                ile: 0.0,
+                leu: 0.0,
+                lys: 1.0,
+                phe: 0.0,
+                pro: 0.0,
+                ser: 0.0,
+                thr: 0.0,
+                trp: 0.0,
+                tyr: 0.0,
+                val: 0.0,
+                variants: BTreeMap::new(),
+            },
+            mass: CodonValueConfig {
+                met: 131.1926,
+                stop: 0.0,
+                ala: 71.0788,
+                arg: 156.1875,
+                asn: 114.1038,
+                asp: 115.0886,
+                cys: 103.1388,
+                gln: 128.1307,
+                glu: 129.1155,
+                gly: 57.0519,
+                his: 137.1411,
+                ile: 113.1594,
+                leu: 113.1594,
+                lys: 128.1741,
+                phe: 147.1766,
+                pro: 97.1167,
+                ser: 87.0782,
+                thr: 101.1051,
+                trp: 186.2132,
+                tyr: 163.1760,
+                val: 99.1326,
+                variants: BTreeMap::new(),
+            },
+            helix_propensity: CodonValueConfig {
+                met: 1.45,
+                stop: 0.0,
+                ala: 1.42,
+                arg: 0.98,
+                asn: 0.67,
+                asp: 1.01,
+                cys: 0.70,
+                gln: 1.11,
+                glu: 1.51,
+                gly: 0.57,
+                his: 1.00,
+                ile: 1.08,
+                leu: 1.21,
+                lys: 1.16,
+                phe: 1.13,
+                pro: 0.57,
+                ser: 0.77,
+                thr: 0.83,
+                trp: 1.08,
+                tyr: 0.69,
+                val: 1.06,
+                variants: BTreeMap::new(),
+            },
+            sheet_propensity: CodonValueConfig {
+                met: 1.05,
+                stop: 0.0,
+                ala: 0.83,
+                arg: 0.93,
+                asn: 0.89,
+                asp: 0.54,
+                cys: 1.19,
+                gln: 1.10,
+                glu: 0.37,
+                gly: 0.75,
+                his: 0.87,
+                ile: 1.60,
+                leu: 1.30,
+                lys: 0.74,
+                phe: 1.38,
+                pro: 0.55,
+                ser: 0.75,
+                thr: 1.19,
+                trp: 1.37,
+                tyr: 1.47,
+                val: 1.70,
+                variants: BTreeMap::new(),
+            },
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use crate::lab::types::splicing::{DEFAULT_ACCEPTOR_MOTIF, DEFAULT_DONOR_MOTIF, SpliceSites};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+/// Upper bound of [`SplicingConfig::max_transcripts`], the alternative transcripts of a sequence
+/// grow combinatorially with its exons.
+pub const MAX_TRANSCRIPTS: usize = 64;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SplicingConfig {
+    #[serde(default = "default_donor")]
+    pub donor: String,
+    #[serde(default = "default_acceptor")]
+    pub acceptor: String,
+    /// Transcripts produced per sequence through alternative splicing, 1 disables it. At most
+    /// [`MAX_TRANSCRIPTS`].
+    #[serde(default = "default_max_transcripts")]
+    pub max_transcripts: usize,
+}
+
+fn default_donor() -> String {
+    DEFAULT_DONOR_MOTIF.to_string()
+}
+
+fn default_acceptor() -> String {
+    DEFAULT_ACCEPTOR_MOTIF.to_string()
+}
+
+fn default_max_transcripts() -> usize {
+    1
+}
+
+impl Default for SplicingConfig {
+    fn default() -> Self {
+        Self {
+            donor: default_donor(),
+            acceptor: default_acceptor(),
+            max_transcripts: default_max_transcripts(),
+        }
+    }
+}
+
+impl SplicingConfig {
+    /// Fails with the first character of the motifs which is not a nucleotide.
+    pub fn get_splice_sites(&self) -> Result<SpliceSites, char> {
+        SpliceSites::from_code_strings(&self.donor, &self.acceptor)
+    }
+}
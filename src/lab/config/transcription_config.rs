@@ -0,0 +1,38 @@
+use crate::lab::types::transcription::{
+    DEFAULT_PROMOTER_MOTIF, DEFAULT_TERMINATOR_MOTIF, TranscriptionMotifs,
+};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct TranscriptionConfig {
+    #[serde(default = "default_promoter")]
+    pub promoter: String,
+    #[serde(default = "default_terminator")]
+    pub terminator: String,
+}
+
+fn default_promoter() -> String {
+    DEFAULT_PROMOTER_MOTIF.to_string()
+}
+
+fn default_terminator() -> String {
+    DEFAULT_TERMINATOR_MOTIF.to_string()
+}
+
+impl Default for TranscriptionConfig {
+    fn default() -> Self {
+        Self {
+            promoter: default_promoter(),
+            terminator: default_terminator(),
+        }
+    }
+}
+
+impl TranscriptionConfig {
+    /// Fails with the first character of the motifs which is not a nucleotide.
+    pub fn get_motifs(&self) -> Result<TranscriptionMotifs, char> {
+        TranscriptionMotifs::from_code_strings(&self.promoter, &self.terminator)
+    }
+}
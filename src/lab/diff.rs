@@ -0,0 +1,642 @@
+use crate::lab::types::codon::{Codon, CodonType};
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use std::fmt::{Display, Formatter};
+
+/// Changes separated by fewer equal nucleotides are checked for being a single inversion.
+const INVERSION_MERGE_GAP: usize = 8;
+
+/// Shorter reverse complements are reported as substitutions.
+const MIN_INVERSION_LENGTH: usize = 4;
+
+/// Hunks separated by shorter common runs are joined.
+const MIN_COMMON_RUN: usize = 4;
+
+/// Hunks chained over short common runs are only merged or checked for an inversion up to this
+/// count, which keeps diffs of unrelated sequences fast.
+const MAX_CHAINED_HUNKS: usize = 32;
+
+/// Longer inversions are reported as substitutions.
+const MAX_INVERSION_LENGTH: usize = 256;
+
+/// A single change of a sequence. Positions refer to the original sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceEdit {
+    Substitution {
+        position: usize,
+        from: Nucleotide,
+        to: Nucleotide,
+    },
+    /// Inserts the nucleotides before the position.
+    Insertion {
+        position: usize,
+        nucleotides: Vec<Nucleotide>,
+    },
+    /// Removes the nucleotides starting at the position, they are kept to detect conflicts.
+    Deletion {
+        position: usize,
+        nucleotides: Vec<Nucleotide>,
+    },
+    /// Replaces the segment by its reverse complement.
+    Inversion { position: usize, length: usize },
+}
+
+impl SequenceEdit {
+    pub fn get_position(&self) -> usize {
+        match self {
+            SequenceEdit::Substitution { position, .. }
+            | SequenceEdit::Insertion { position, .. }
+            | SequenceEdit::Deletion { position, .. }
+            | SequenceEdit::Inversion { position, .. } => *position,
+        }
+    }
+
+    /// Amount of original nucleotides the edit replaces.
+    pub fn get_original_length(&self) -> usize {
+        match self {
+            SequenceEdit::Substitution { .. } => 1,
+            SequenceEdit::Insertion { .. } => 0,
+            SequenceEdit::Deletion { nucleotides, .. } => nucleotides.len(),
+            SequenceEdit::Inversion { length, .. } => *length,
+        }
+    }
+
+    /// Nucleotides replacing the original ones, the original segment is needed for inversions.
+    fn replacement(&self, original: &[Nucleotide]) -> Vec<Nucleotide> {
+        match self {
+            SequenceEdit::Substitution { to, .. } => vec![*to],
+            SequenceEdit::Insertion { nucleotides, .. } => nucleotides.clone(),
+            SequenceEdit::Deletion { .. } => vec![],
+            SequenceEdit::Inversion { .. } => reverse_complement(original),
+        }
+    }
+}
+
+/// A change of one or more amino acids.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AminoAcidChange {
+    pub codon: usize,
+    pub from: CodonType,
+    pub to: CodonType,
+}
+
+/// Effect of an edit on the protein read in frame from the start of the sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditEffect {
+    /// Only the codons changed, not the amino acids.
+    Synonymous,
+    Missense(Vec<AminoAcidChange>),
+    /// Introduces a stop codon.
+    Nonsense(Vec<AminoAcidChange>),
+    /// Replaces a stop codon.
+    StopLost(Vec<AminoAcidChange>),
+    Frameshift {
+        codon: usize,
+    },
+    InFrameInsertion {
+        codon: usize,
+        codons: usize,
+    },
+    InFrameDeletion {
+        codon: usize,
+        codons: usize,
+    },
+    /// The edit only touches the trailing nucleotides which don't form a codon, or reaches past
+    /// the end of the sequence.
+    NonCoding,
+}
+
+impl Display for AminoAcidChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}{}{:?}", self.from, self.codon, self.to)
+    }
+}
+
+impl Display for EditEffect {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let changes = |changes: &[AminoAcidChange]| {
+            changes
+                .iter()
+                .map(|change| change.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+
+        match self {
+            EditEffect::Synonymous => write!(f, "synonymous"),
+            EditEffect::Missense(list) => write!(f, "missense {}", changes(list)),
+            EditEffect::Nonsense(list) => write!(f, "nonsense {}", changes(list)),
+            EditEffect::StopLost(list) => write!(f, "stop lost {}", changes(list)),
+            EditEffect::Frameshift { codon } => write!(f, "frameshift at codon {}", codon),
+            EditEffect::InFrameInsertion { codon, codons } => {
+                write!(f, "{} codons inserted at codon {}", codons, codon)
+            }
+            EditEffect::InFrameDeletion { codon, codons } => {
+                write!(f, "{} codons deleted at codon {}", codons, codon)
+            }
+            EditEffect::NonCoding => write!(f, "non-coding"),
+        }
+    }
+}
+
+/// Edits turning one sequence into another, sorted by position and not overlapping.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SequenceDiff {
+    edits: Vec<SequenceEdit>,
+}
+
+/// Why an edit can't be applied to a sequence.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditConflict {
+    pub position: usize,
+    pub reason: String,
+}
+
+impl SequenceDiff {
+    pub fn new(mut edits: Vec<SequenceEdit>) -> Self {
+        edits.sort_by_key(|edit| edit.get_position());
+        Self { edits }
+    }
+
+    pub fn get_edits(&self) -> &[SequenceEdit] {
+        &self.edits
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Computes a minimal diff with Myers' algorithm. Substitutions are preferred over indels of
+    /// the same length, segments replaced by their reverse complement become inversions.
+    pub fn between(original: &NucleotideSequence, target: &NucleotideSequence) -> Self {
+        let a = original.get_sequence();
+        let b = target.get_sequence();
+        let hunks = merge_substitutions(a, b, merge_close_hunks(find_hunks(a, b)));
+
+        let mut edits = Vec::new();
+        let mut index = 0;
+        let mut previous_end = 0;
+        while index < hunks.len() {
+            if let Some((inversion, consumed)) = find_inversion(a, b, &hunks[index..], previous_end)
+            {
+                previous_end = inversion.get_position() + inversion.get_original_length();
+                edits.push(inversion);
+                index += consumed;
+                continue;
+            }
+            push_hunk_edits(a, b, hunks[index], &mut edits);
+            previous_end = hunks[index].a_end;
+            index += 1;
+        }
+
+        Self::new(edits)
+    }
+
+    pub fn apply(&self, original: &NucleotideSequence) -> Result<NucleotideSequence, EditConflict> {
+        let a = original.get_sequence();
+        let mut result = Vec::with_capacity(a.len());
+        let mut cursor = 0;
+
+        for edit in &self.edits {
+            let position = edit.get_position();
+            let Some(end) = position.checked_add(edit.get_original_length()) else {
+                return Err(EditConflict {
+                    position,
+                    reason: "edit length out of range".to_string(),
+                });
+            };
+            if position < cursor {
+                return Err(EditConflict {
+                    position,
+                    reason: "overlaps the previous edit".to_string(),
+                });
+            }
+            let Some(segment) = a.get(position..end) else {
+                return Err(EditConflict {
+                    position,
+                    reason: format!("exceeds the sequence length {}", a.len()),
+                });
+            };
+
+            match edit {
+                SequenceEdit::Substitution { from, .. } if segment != [*from] => {
+                    return Err(EditConflict {
+                        position,
+                        reason: format!("expected {}", char::from(*from)),
+                    });
+                }
+                SequenceEdit::Deletion { nucleotides, .. } if segment != nucleotides.as_slice() => {
+                    return Err(EditConflict {
+                        position,
+                        reason: "deleted nucleotides differ".to_string(),
+                    });
+                }
+                _ => {}
+            }
+
+            result.extend_from_slice(&a[cursor..position]);
+            result.extend(edit.replacement(segment));
+            cursor = end;
+        }
+
+        result.extend_from_slice(&a[cursor..]);
+        Ok(NucleotideSequence::new(result))
+    }
+
+    /// Effect of every edit on its own, codons are counted from the start of the original
+    /// sequence.
+    pub fn annotate(&self, original: &NucleotideSequence) -> Vec<(SequenceEdit, EditEffect)> {
+        self.edits
+            .iter()
+            .map(|edit| (edit.clone(), edit_effect(original.get_sequence(), edit)))
+            .collect()
+    }
+}
+
+pub fn reverse_complement(nucleotides: &[Nucleotide]) -> Vec<Nucleotide> {
+    nucleotides
+        .iter()
+        .rev()
+        .map(|nucleotide| nucleotide.complement())
+        .collect()
+}
+
+/// Changed region, half-open ranges in the original (`a`) and target (`b`) sequence.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Hunk {
+    a_start: usize,
+    a_end: usize,
+    b_start: usize,
+    b_end: usize,
+}
+
+/// Myers' O(ND) diff in linear space, returning the regions between common nucleotides.
+fn find_hunks(a: &[Nucleotide], b: &[Nucleotide]) -> Vec<Hunk> {
+    let mut deleted = vec![false; a.len()];
+    let mut inserted = vec![false; b.len()];
+    mark_changes(a, b, &mut deleted, &mut inserted);
+
+    // Pair up the runs of changes between common nucleotides
+    let mut hunks = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && !deleted[i] && !inserted[j] {
+            i += 1;
+            j += 1;
+            continue;
+        }
+        let (a_start, b_start) = (i, j);
+        while i < a.len() && deleted[i] {
+            i += 1;
+        }
+        while j < b.len() && inserted[j] {
+            j += 1;
+        }
+        hunks.push(Hunk {
+            a_start,
+            a_end: i,
+            b_start,
+            b_end: j,
+        });
+    }
+    hunks
+}
+
+/// Marks the deleted and inserted positions of a shortest edit script, splitting the sequences at
+/// the middle snake of the edit graph and recursing into both halves.
+fn mark_changes(a: &[Nucleotide], b: &[Nucleotide], deleted: &mut [bool], inserted: &mut [bool]) {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|(x, y)| x == y)
+        .count();
+    let (a_end, b_end) = (a.len() - suffix, b.len() - suffix);
+    let (a, b) = (&a[prefix..a_end], &b[prefix..b_end]);
+    let (deleted, inserted) = (&mut deleted[prefix..a_end], &mut inserted[prefix..b_end]);
+
+    if a.is_empty() || b.is_empty() {
+        deleted.fill(true);
+        inserted.fill(true);
+        return;
+    }
+
+    // Both sequences differ at their ends, so at least two edits are left and both halves are
+    // smaller problems
+    let (x, y, u, v) = middle_snake(a, b);
+    let (deleted_head, deleted_tail) = deleted.split_at_mut(x);
+    let (inserted_head, inserted_tail) = inserted.split_at_mut(y);
+    mark_changes(&a[..x], &b[..y], deleted_head, inserted_head);
+    mark_changes(
+        &a[u..],
+        &b[v..],
+        &mut deleted_tail[u - x..],
+        &mut inserted_tail[v - y..],
+    );
+}
+
+/// Searches the shortest edit path from both ends at once and returns the snake `(x, y)` to
+/// `(u, v)` where the searches meet, which lies on a shortest path.
+fn middle_snake(a: &[Nucleotide], b: &[Nucleotide]) -> (usize, usize, usize, usize) {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let delta = n - m;
+    let odd = delta % 2 != 0;
+    let max = (n + m + 1) / 2;
+    let offset = max + 1;
+    // Furthest x on each diagonal, the backward search runs on the reversed sequences
+    let mut forward = vec![0isize; 2 * max as usize + 3];
+    let mut backward = vec![0isize; 2 * max as usize + 3];
+
+    for d in 0..=max {
+        for k in (-d..=d).step_by(2) {
+            let index = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && forward[index - 1] < forward[index + 1]) {
+                forward[index + 1]
+            } else {
+                forward[index - 1] + 1
+            };
+            let (start_x, start_y) = (x, x - k);
+            let mut y = start_y;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            forward[index] = x;
+
+            let reverse_k = delta - k;
+            if odd && reverse_k.abs() < d && x + backward[(offset + reverse_k) as usize] >= n {
+                return (start_x as usize, start_y as usize, x as usize, y as usize);
+            }
+        }
+
+        for k in (-d..=d).step_by(2) {
+            let index = (offset + k) as usize;
+            let mut x = if k == -d || (k != d && backward[index - 1] < backward[index + 1]) {
+                backward[index + 1]
+            } else {
+                backward[index - 1] + 1
+            };
+            let (end_x, end_y) = (x, x - k);
+            let mut y = end_y;
+            while x < n && y < m && a[(n - 1 - x) as usize] == b[(m - 1 - y) as usize] {
+                x += 1;
+                y += 1;
+            }
+            backward[index] = x;
+
+            let forward_k = delta - k;
+            if !odd && forward_k.abs() <= d && x + forward[(offset + forward_k) as usize] >= n {
+                return (
+                    (n - x) as usize,
+                    (m - y) as usize,
+                    (n - end_x) as usize,
+                    (m - end_y) as usize,
+                );
+            }
+        }
+    }
+    unreachable!("the searches meet after at most (n + m) / 2 rounds")
+}
+
+/// Joins hunks separated by fewer than [`MIN_COMMON_RUN`] common nucleotides. Minimal diffs of
+/// unrelated segments match scattered single nucleotides, a few more changed nucleotides are
+/// shorter than the many edits between them.
+fn merge_close_hunks(hunks: Vec<Hunk>) -> Vec<Hunk> {
+    let mut merged: Vec<Hunk> = Vec::with_capacity(hunks.len());
+    for hunk in hunks {
+        match merged.last_mut() {
+            Some(last) if hunk.a_start - last.a_end < MIN_COMMON_RUN => {
+                last.a_end = hunk.a_end;
+                last.b_end = hunk.b_end;
+            }
+            _ => merged.push(hunk),
+        }
+    }
+    merged
+}
+
+/// Myers' algorithm doesn't distinguish substitutions from deletions and insertions nearby. An
+/// unbalanced hunk is merged with the following ones if the merged region has the same length in
+/// both sequences and changes at most as many nucleotides as the separate hunks.
+fn merge_substitutions(a: &[Nucleotide], b: &[Nucleotide], hunks: Vec<Hunk>) -> Vec<Hunk> {
+    let is_balanced = |hunk: &Hunk| hunk.a_end - hunk.a_start == hunk.b_end - hunk.b_start;
+    let cost = |hunk: &Hunk| (hunk.a_end - hunk.a_start).max(hunk.b_end - hunk.b_start);
+
+    let mut merged = Vec::with_capacity(hunks.len());
+    let mut index = 0;
+    while index < hunks.len() {
+        let first = hunks[index];
+        let mut next_index = index + 1;
+
+        if !is_balanced(&first) {
+            let mut separate_cost = cost(&first);
+            let chained = hunks[index + 1..].iter().take(MAX_CHAINED_HUNKS - 1);
+            for (offset, hunk) in chained.enumerate() {
+                if hunk.a_start - hunks[index + offset].a_end >= INVERSION_MERGE_GAP {
+                    break;
+                }
+                separate_cost += cost(hunk);
+
+                let candidate = Hunk {
+                    a_end: hunk.a_end,
+                    b_end: hunk.b_end,
+                    ..first
+                };
+                let differences = a[candidate.a_start..candidate.a_end]
+                    .iter()
+                    .zip(&b[candidate.b_start..candidate.b_end])
+                    .filter(|(x, y)| x != y)
+                    .count();
+                if is_balanced(&candidate) && differences <= separate_cost {
+                    merged.push(candidate);
+                    next_index = index + offset + 2;
+                    break;
+                }
+            }
+        }
+
+        if next_index == index + 1 {
+            merged.push(first);
+        }
+        index = next_index;
+    }
+    merged
+}
+
+/// Checks whether the first hunks, merged over short common runs and extended into the common
+/// nucleotides around them (but not before `previous_end`), replace a segment by its reverse
+/// complement. Returns the inversion and the amount of hunks it covers.
+fn find_inversion(
+    a: &[Nucleotide],
+    b: &[Nucleotide],
+    hunks: &[Hunk],
+    previous_end: usize,
+) -> Option<(SequenceEdit, usize)> {
+    let first = hunks[0];
+    let max_left = INVERSION_MERGE_GAP.min(first.a_start - previous_end);
+
+    for (count, last) in hunks.iter().enumerate().take(MAX_CHAINED_HUNKS) {
+        if count > 0 && last.a_start - hunks[count - 1].a_end >= INVERSION_MERGE_GAP {
+            break;
+        }
+        if last.a_end - first.a_start > MAX_INVERSION_LENGTH {
+            break;
+        }
+        if last.a_end - first.a_start != last.b_end - first.b_start {
+            continue;
+        }
+
+        let max_right = INVERSION_MERGE_GAP.min(common_after(a, hunks, count));
+        for left in 0..=max_left {
+            let a_start = first.a_start - left;
+            let b_start = first.b_start - left;
+            for right in 0..=max_right {
+                let a_end = last.a_end + right;
+                let b_end = last.b_end + right;
+                if a_end - a_start < MIN_INVERSION_LENGTH {
+                    continue;
+                }
+                if is_reverse_complement(&a[a_start..a_end], &b[b_start..b_end]) {
+                    let inversion = SequenceEdit::Inversion {
+                        position: a_start,
+                        length: a_end - a_start,
+                    };
+                    return Some((inversion, count + 1));
+                }
+            }
+        }
+    }
+    None
+}
+
+fn is_reverse_complement(original: &[Nucleotide], target: &[Nucleotide]) -> bool {
+    original.len() == target.len()
+        && original
+            .iter()
+            .rev()
+            .zip(target)
+            .all(|(x, y)| x.complement() == *y)
+}
+
+/// Common nucleotides after the hunk at the index, up to the next hunk or the end.
+fn common_after(a: &[Nucleotide], hunks: &[Hunk], index: usize) -> usize {
+    hunks
+        .get(index + 1)
+        .map(|next| next.a_start)
+        .unwrap_or(a.len())
+        - hunks[index].a_end
+}
+
+fn push_hunk_edits(a: &[Nucleotide], b: &[Nucleotide], hunk: Hunk, edits: &mut Vec<SequenceEdit>) {
+    let a_length = hunk.a_end - hunk.a_start;
+    let b_length = hunk.b_end - hunk.b_start;
+    let shared = a_length.min(b_length);
+
+    for offset in 0..shared {
+        let (from, to) = (a[hunk.a_start + offset], b[hunk.b_start + offset]);
+        if from != to {
+            edits.push(SequenceEdit::Substitution {
+                position: hunk.a_start + offset,
+                from,
+                to,
+            });
+        }
+    }
+    if a_length > shared {
+        edits.push(SequenceEdit::Deletion {
+            position: hunk.a_start + shared,
+            nucleotides: a[hunk.a_start + shared..hunk.a_end].to_vec(),
+        });
+    }
+    if b_length > shared {
+        edits.push(SequenceEdit::Insertion {
+            position: hunk.a_end,
+            nucleotides: b[hunk.b_start + shared..hunk.b_end].to_vec(),
+        });
+    }
+}
+
+fn edit_effect(original: &[Nucleotide], edit: &SequenceEdit) -> EditEffect {
+    let position = edit.get_position();
+    let codon = position / 3;
+    let coding_length = original.len() - original.len() % 3;
+
+    match edit {
+        SequenceEdit::Insertion { nucleotides, .. }
+        | SequenceEdit::Deletion { nucleotides, .. }
+            if position >= coding_length && !nucleotides.len().is_multiple_of(3) =>
+        {
+            EditEffect::NonCoding
+        }
+        SequenceEdit::Insertion { nucleotides, .. } if nucleotides.len().is_multiple_of(3) => {
+            EditEffect::InFrameInsertion {
+                codon,
+                codons: nucleotides.len() / 3,
+            }
+        }
+        SequenceEdit::Deletion { nucleotides, .. } if nucleotides.len().is_multiple_of(3) => {
+            EditEffect::InFrameDeletion {
+                codon,
+                codons: nucleotides.len() / 3,
+            }
+        }
+        SequenceEdit::Insertion { .. } | SequenceEdit::Deletion { .. } => {
+            EditEffect::Frameshift { codon }
+        }
+        SequenceEdit::Substitution { .. } | SequenceEdit::Inversion { .. } => {
+            // Edits of parsed patches may reach past the sequence they are annotated with
+            let Some(edit_end) = position
+                .checked_add(edit.get_original_length())
+                .filter(|edit_end| *edit_end <= original.len())
+            else {
+                return EditEffect::NonCoding;
+            };
+            if position >= coding_length {
+                return EditEffect::NonCoding;
+            }
+            let end = edit_end.min(coding_length);
+            let start = codon * 3;
+            let end = end.div_ceil(3) * 3;
+
+            let mut changed = original[start..end].to_vec();
+            let segment = &original[position..edit_end];
+            changed.splice(
+                position - start..position - start + segment.len().min(end - position),
+                edit.replacement(segment).into_iter().take(end - position),
+            );
+            amino_acid_effect(codon, &original[start..end], &changed)
+        }
+    }
+}
+
+fn amino_acid_effect(
+    first_codon: usize,
+    original: &[Nucleotide],
+    changed: &[Nucleotide],
+) -> EditEffect {
+    let changes: Vec<AminoAcidChange> = original
+        .chunks_exact(3)
+        .zip(changed.chunks_exact(3))
+        .enumerate()
+        .filter_map(|(index, (from, to))| {
+            let from = Codon::from_nucleotides(from)?.get_type();
+            let to = Codon::from_nucleotides(to)?.get_type();
+            (from != to).then_some(AminoAcidChange {
+                codon: first_codon + index,
+                from,
+                to,
+            })
+        })
+        .collect();
+
+    if changes.is_empty() {
+        EditEffect::Synonymous
+    } else if changes.iter().any(|change| change.to == CodonType::Stop) {
+        EditEffect::Nonsense(changes)
+    } else if changes.iter().any(|change| change.from == CodonType::Stop) {
+        EditEffect::StopLost(changes)
+    } else {
+        EditEffect::Missense(changes)
+    }
+}
@@ -0,0 +1,438 @@
+use crate::lab::config::config_format::ConfigFormat;
+use crate::lab::types::codon::CodonType;
+use crate::lab::types::genome::MAX_NAME_LENGTH;
+use std::fmt::{Display, Formatter};
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LabError {
+    DuplicateGeneId(Vec<CodonType>),
+    AmbiguousGeneId {
+        id: Vec<CodonType>,
+        conflicting_id: Vec<CodonType>,
+    },
+}
+
+impl Display for LabError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LabError::DuplicateGeneId(id) => {
+                write!(f, "gene type id {:?} is already registered", id)
+            }
+            LabError::AmbiguousGeneId { id, conflicting_id } => write!(
+                f,
+                "gene type id {:?} is ambiguous with registered id {:?}, one is a prefix of the other",
+                id, conflicting_id
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LabError {}
+
+#[derive(Debug)]
+pub enum GenomeError {
+    Io(std::io::Error),
+    InvalidBytes(String),
+    /// Line numbers start at 1, 0 refers to the whole text.
+    InvalidText {
+        line: usize,
+        reason: String,
+    },
+    UnknownChromosome(String),
+    DuplicateChromosome(String),
+    /// Length of the name in bytes.
+    NameTooLong(usize),
+    OutOfBounds {
+        chromosome: String,
+        position: usize,
+        length: usize,
+    },
+}
+
+impl Display for GenomeError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GenomeError::Io(error) => write!(f, "failed to access genome file: {}", error),
+            GenomeError::InvalidBytes(reason) => write!(f, "invalid genome bytes: {}", reason),
+            GenomeError::InvalidText { line, reason } => {
+                write!(f, "invalid genome text at line {}: {}", line, reason)
+            }
+            GenomeError::UnknownChromosome(name) => write!(f, "unknown chromosome '{}'", name),
+            GenomeError::DuplicateChromosome(name) => {
+                write!(f, "chromosome '{}' already exists", name)
+            }
+            GenomeError::NameTooLong(length) => write!(
+                f,
+                "chromosome name of {} bytes is longer than {} bytes",
+                length, MAX_NAME_LENGTH
+            ),
+            GenomeError::OutOfBounds {
+                chromosome,
+                position,
+                length,
+            } => write!(
+                f,
+                "position {} is out of bounds for chromosome '{}' of length {}",
+                position, chromosome, length
+            ),
+        }
+    }
+}
+
+impl std::error::Error for GenomeError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            GenomeError::Io(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for GenomeError {
+    fn from(error: std::io::Error) -> Self {
+        GenomeError::Io(error)
+    }
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    /// The origin is the file or environment variable the invalid content came from.
+    Parse {
+        origin: String,
+        message: String,
+    },
+    Validation(Vec<String>),
+    Serialize {
+        format: ConfigFormat,
+        message: String,
+    },
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigError::Io { path, source } => {
+                write!(
+                    f,
+                    "failed to access config '{}': {}",
+                    path.display(),
+                    source
+                )
+            }
+            ConfigError::Parse { origin, message } => {
+                write!(f, "failed to parse config from {}: {}", origin, message)
+            }
+            ConfigError::Validation(problems) => {
+                write!(f, "invalid config: {}", problems.join("; "))
+            }
+            ConfigError::Serialize { format, message } => write!(
+                f,
+                "failed to write config as {}: {}",
+                format.get_extension(),
+                message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ConfigError::Io { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum SnapshotError {
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    Parse {
+        origin: String,
+        message: String,
+    },
+    Serialize(String),
+    UnsupportedVersion(u32),
+    /// The gene type with this name doesn't support snapshots.
+    UnsupportedGeneType(String),
+    UnknownGeneTypeTag(String),
+    InvalidGeneType {
+        tag: String,
+        message: String,
+    },
+    Config(ConfigError),
+    Lab(LabError),
+}
+
+impl Display for SnapshotError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SnapshotError::Io { path, source } => {
+                write!(
+                    f,
+                    "failed to access snapshot '{}': {}",
+                    path.display(),
+                    source
+                )
+            }
+            SnapshotError::Parse { origin, message } => {
+                write!(f, "failed to parse snapshot from {}: {}", origin, message)
+            }
+            SnapshotError::Serialize(message) => {
+                write!(f, "failed to write snapshot: {}", message)
+            }
+            SnapshotError::UnsupportedVersion(version) => {
+                write!(f, "snapshot version {} is not supported", version)
+            }
+            SnapshotError::UnsupportedGeneType(name) => {
+                write!(f, "gene type '{}' does not support snapshots", name)
+            }
+            SnapshotError::UnknownGeneTypeTag(tag) => {
+                write!(f, "no gene type is registered for tag '{}'", tag)
+            }
+            SnapshotError::InvalidGeneType { tag, message } => {
+                write!(f, "invalid gene type with tag '{}': {}", tag, message)
+            }
+            SnapshotError::Config(error) => write!(f, "{}", error),
+            SnapshotError::Lab(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SnapshotError::Io { source, .. } => Some(source),
+            SnapshotError::Config(error) => Some(error),
+            SnapshotError::Lab(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ConfigError> for SnapshotError {
+    fn from(error: ConfigError) -> Self {
+        SnapshotError::Config(error)
+    }
+}
+
+impl From<LabError> for SnapshotError {
+    fn from(error: LabError) -> Self {
+        SnapshotError::Lab(error)
+    }
+}
+
+#[derive(Debug)]
+pub enum SessionError {
+    UnknownCommand(String),
+    /// The arguments don't match the command, contains the usage of the command.
+    Usage(String),
+    InvalidArgument(String),
+    UnknownGenome(String),
+    UnknownChromosome(String),
+    Genome(GenomeError),
+    Config(ConfigError),
+    Patch(PatchError),
+    Motif(MotifError),
+}
+
+impl Display for SessionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionError::UnknownCommand(command) => {
+                write!(f, "unknown command '{}', try 'help'", command)
+            }
+            SessionError::Usage(usage) => write!(f, "usage: {}", usage),
+            SessionError::InvalidArgument(argument) => {
+                write!(f, "invalid argument '{}'", argument)
+            }
+            SessionError::UnknownGenome(name) => write!(f, "unknown genome '{}'", name),
+            SessionError::UnknownChromosome(name) => write!(f, "unknown chromosome '{}'", name),
+            SessionError::Genome(error) => write!(f, "{}", error),
+            SessionError::Config(error) => write!(f, "{}", error),
+            SessionError::Patch(error) => write!(f, "{}", error),
+            SessionError::Motif(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for SessionError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            SessionError::Genome(error) => Some(error),
+            SessionError::Config(error) => Some(error),
+            SessionError::Patch(error) => Some(error),
+            SessionError::Motif(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<GenomeError> for SessionError {
+    fn from(error: GenomeError) -> Self {
+        SessionError::Genome(error)
+    }
+}
+
+impl From<ConfigError> for SessionError {
+    fn from(error: ConfigError) -> Self {
+        SessionError::Config(error)
+    }
+}
+
+impl From<MotifError> for SessionError {
+    fn from(error: MotifError) -> Self {
+        SessionError::Motif(error)
+    }
+}
+
+#[derive(Debug)]
+pub enum PatchError {
+    Parse {
+        line: usize,
+        reason: String,
+    },
+    UnknownChromosome(String),
+    /// The patch doesn't fit the genome, e.g. a substituted nucleotide differs.
+    Conflict {
+        chromosome: String,
+        position: usize,
+        reason: String,
+    },
+    Genome(GenomeError),
+}
+
+impl Display for PatchError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PatchError::Parse { line, reason } => {
+                write!(f, "invalid patch at line {}: {}", line, reason)
+            }
+            PatchError::UnknownChromosome(name) => {
+                write!(f, "patched chromosome '{}' does not exist", name)
+            }
+            PatchError::Conflict {
+                chromosome,
+                position,
+                reason,
+            } => write!(
+                f,
+                "patch conflict in chromosome '{}' at position {}: {}",
+                chromosome, position, reason
+            ),
+            PatchError::Genome(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for PatchError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PatchError::Genome(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<GenomeError> for PatchError {
+    fn from(error: GenomeError) -> Self {
+        PatchError::Genome(error)
+    }
+}
+
+impl From<PatchError> for SessionError {
+    fn from(error: PatchError) -> Self {
+        SessionError::Patch(error)
+    }
+}
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    Io(std::io::Error),
+    /// The file is not a population archive or is damaged.
+    InvalidData(String),
+    OutOfRange {
+        index: usize,
+        length: usize,
+    },
+    Patch(PatchError),
+    Genome(GenomeError),
+}
+
+impl Display for ArchiveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ArchiveError::Io(error) => write!(f, "failed to access archive: {}", error),
+            ArchiveError::InvalidData(reason) => write!(f, "invalid archive: {}", reason),
+            ArchiveError::OutOfRange { index, length } => write!(
+                f,
+                "archive member {} out of range, the archive has {} members",
+                index, length
+            ),
+            ArchiveError::Patch(error) => write!(f, "{}", error),
+            ArchiveError::Genome(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ArchiveError::Io(error) => Some(error),
+            ArchiveError::Patch(error) => Some(error),
+            ArchiveError::Genome(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ArchiveError {
+    fn from(error: std::io::Error) -> Self {
+        ArchiveError::Io(error)
+    }
+}
+
+impl From<PatchError> for ArchiveError {
+    fn from(error: PatchError) -> Self {
+        ArchiveError::Patch(error)
+    }
+}
+
+impl From<GenomeError> for ArchiveError {
+    fn from(error: GenomeError) -> Self {
+        ArchiveError::Genome(error)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MotifError {
+    /// Position is the character index in the pattern.
+    Parse {
+        position: usize,
+        reason: String,
+    },
+    InvalidMatrix(String),
+}
+
+impl Display for MotifError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MotifError::Parse { position, reason } => {
+                write!(f, "invalid pattern at {}: {}", position, reason)
+            }
+            MotifError::InvalidMatrix(reason) => {
+                write!(f, "invalid position weight matrix: {}", reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for MotifError {}
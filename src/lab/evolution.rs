@@ -0,0 +1,142 @@
+use crate::lab::Lab;
+use crate::lab::mutation::{ChromosomeMutation, substitute_random, to_probability};
+use crate::lab::reproduction::cross;
+use crate::lab::types::genome::Genome;
+use crate::lab::types::phenotype::Phenotype;
+use rand::seq::IndexedRandom;
+use rand::{Rng, rng};
+
+/// Rates and selection parameters of an [`Evolution`]. Rates are clamped to 0..=1, NaN counts as 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EvolutionSettings {
+    /// Probability of a nucleotide substitution per nucleotide and generation.
+    pub mutation_rate: f64,
+    /// Probability of a structural mutation per genome and generation.
+    pub chromosome_mutation_rate: f64,
+    /// Amount of fittest genomes copied unchanged into the next generation.
+    pub elite: usize,
+    /// Amount of genomes competing for each parent slot.
+    pub tournament_size: usize,
+}
+
+impl Default for EvolutionSettings {
+    fn default() -> Self {
+        Self {
+            mutation_rate: 0.001,
+            chromosome_mutation_rate: 0.01,
+            elite: 1,
+            tournament_size: 2,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenerationStats {
+    pub generation: usize,
+    pub best_fitness: f64,
+    pub mean_fitness: f64,
+}
+
+/// A population of genomes evolving under a fitness function of their phenotype.
+#[derive(Debug, Clone)]
+pub struct Evolution {
+    population: Vec<Genome>,
+    settings: EvolutionSettings,
+    generation: usize,
+}
+
+impl Evolution {
+    pub fn new(population: Vec<Genome>, settings: EvolutionSettings) -> Self {
+        Self {
+            population,
+            settings,
+            generation: 0,
+        }
+    }
+
+    pub fn get_population(&self) -> &[Genome] {
+        &self.population
+    }
+
+    pub fn get_generation(&self) -> usize {
+        self.generation
+    }
+
+    /// Evaluates the current generation and replaces it by its offspring, the returned stats
+    /// describe the evaluated generation.
+    pub fn step(&mut self, lab: &Lab, fitness: &dyn Fn(&Phenotype) -> f64) -> GenerationStats {
+        let mut scored = self.evaluate(lab, fitness);
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let stats = GenerationStats {
+            generation: self.generation,
+            best_fitness: scored.first().map(|(_, score)| *score).unwrap_or_default(),
+            mean_fitness: scored.iter().map(|(_, score)| score).sum::<f64>()
+                / scored.len().max(1) as f64,
+        };
+
+        let mut rng = rng();
+        let mut next: Vec<Genome> = scored
+            .iter()
+            .take(self.settings.elite)
+            .map(|(genome, _)| genome.clone())
+            .collect();
+        while next.len() < scored.len() {
+            let first = self.select(&scored);
+            let second = self.select(&scored);
+            let mut offspring = cross(first, second);
+
+            substitute_random(&mut offspring, self.settings.mutation_rate);
+            if rng.random_bool(to_probability(self.settings.chromosome_mutation_rate))
+                && let Some(mutation) = ChromosomeMutation::random(&offspring)
+            {
+                // A failed mutation leaves the genome untouched
+                let _ = mutation.apply(&mut offspring);
+            }
+            next.push(offspring);
+        }
+
+        self.population = next;
+        self.generation += 1;
+        stats
+    }
+
+    /// Genomes paired with their fitness, in population order.
+    pub fn evaluate(&self, lab: &Lab, fitness: &dyn Fn(&Phenotype) -> f64) -> Vec<(Genome, f64)> {
+        self.population
+            .iter()
+            .map(|genome| (genome.clone(), fitness(&lab.express_genome(genome))))
+            .collect()
+    }
+
+    /// The fittest genome of the current generation.
+    pub fn get_best(&self, lab: &Lab, fitness: &dyn Fn(&Phenotype) -> f64) -> Option<Genome> {
+        self.evaluate(lab, fitness)
+            .into_iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(genome, _)| genome)
+    }
+
+    fn select<'a>(&self, scored: &'a [(Genome, f64)]) -> &'a Genome {
+        let mut rng = rng();
+        let candidates: Vec<&(Genome, f64)> = scored
+            .choose_multiple(&mut rng, self.settings.tournament_size.max(1))
+            .collect();
+        &candidates
+            .iter()
+            .max_by(|a, b| a.1.total_cmp(&b.1))
+            .expect("Population must not be empty")
+            .0
+    }
+}
+
+/// Fitness which is the sum of a phenotype property over all nodes.
+pub fn property_fitness(property: &str) -> impl Fn(&Phenotype) -> f64 + '_ {
+    move |phenotype| {
+        phenotype
+            .get_nodes()
+            .values()
+            .filter_map(|node| node.get_property(property))
+            .sum()
+    }
+}
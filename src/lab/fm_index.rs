@@ -0,0 +1,465 @@
+use crate::lab::error::GenomeError;
+use crate::lab::types::genome::Genome;
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::sequence_serde::BASE64_PREFIX;
+use ::base64::Engine;
+use ::base64::engine::general_purpose::STANDARD;
+use serde::de::{Error, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+use std::path::Path;
+
+const FM_INDEX_MAGIC: &[u8; 4] = b"BLFM";
+const GENOME_INDEX_MAGIC: &[u8; 4] = b"BLGI";
+
+/// Rows between two stored nucleotide counts, two packed words of the BWT.
+const OCC_SAMPLE_RATE: usize = 64;
+/// Every suffix starting at a multiple of this is stored, locating walks at most this many rows.
+const SA_SAMPLE_RATE: usize = 32;
+const SYMBOLS_PER_WORD: usize = 32;
+const LOW_BITS: u64 = 0x5555_5555_5555_5555;
+
+/// FM-index of a nucleotide sequence, counting and locating exact occurrences of a query in time
+/// proportional to the query length (locating adds a constant per occurrence).
+///
+/// Only the Burrows-Wheeler transform is serialized, the rest of the index is restored from it in
+/// linear time. Building sorts all suffixes and is meant to be done once per sequence.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FmIndex {
+    length: usize,
+    /// Burrows-Wheeler transform packed 2 bits per row, the sentinel row holds an `A`.
+    bwt: Vec<u64>,
+    sentinel_row: usize,
+    /// First row of the suffixes starting with each nucleotide.
+    first_rows: [usize; 4],
+    /// Nucleotide counts in the BWT before every multiple of [`OCC_SAMPLE_RATE`].
+    occ_samples: Vec<[u32; 4]>,
+    sampled_rows: Vec<u64>,
+    /// Sampled rows before each word of `sampled_rows`.
+    sampled_ranks: Vec<u32>,
+    /// Suffix array entries of the sampled rows, in row order.
+    sa_samples: Vec<u32>,
+}
+
+impl FmIndex {
+    pub fn new(sequence: &NucleotideSequence) -> Self {
+        Self::from_nucleotides(sequence.get_sequence())
+    }
+
+    pub fn from_nucleotides(nucleotides: &[Nucleotide]) -> Self {
+        assert!(
+            nucleotides.len() < u32::MAX as usize,
+            "Sequence too long for an FM-index"
+        );
+
+        let text: Vec<u8> = nucleotides
+            .iter()
+            .map(|nucleotide| *nucleotide as u8 + 1)
+            .chain([0])
+            .collect();
+        let suffix_array = suffix_array(&text);
+
+        let mut bwt = vec![0u64; text.len().div_ceil(SYMBOLS_PER_WORD)];
+        let mut sentinel_row = 0;
+        for (row, suffix) in suffix_array.iter().enumerate() {
+            match *suffix as usize {
+                0 => sentinel_row = row,
+                suffix => set_symbol(&mut bwt, row, text[suffix - 1] - 1),
+            }
+        }
+
+        Self::from_bwt(nucleotides.len(), bwt, sentinel_row)
+            .expect("BWT of a sequence is always valid")
+    }
+
+    /// Length of the indexed sequence.
+    pub fn len(&self) -> usize {
+        self.length
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.length == 0
+    }
+
+    /// Number of occurrences of the query, empty queries match nothing.
+    pub fn count(&self, query: &[Nucleotide]) -> usize {
+        self.find_rows(query).map_or(0, |(start, end)| end - start)
+    }
+
+    pub fn contains(&self, query: &[Nucleotide]) -> bool {
+        self.count(query) > 0
+    }
+
+    /// Sorted start positions of all occurrences of the query.
+    pub fn locate(&self, query: &[Nucleotide]) -> Vec<usize> {
+        let Some((start, end)) = self.find_rows(query) else {
+            return Vec::new();
+        };
+
+        let mut positions: Vec<usize> = (start..end).map(|row| self.suffix_at(row)).collect();
+        positions.sort_unstable();
+        positions
+    }
+
+    /// Layout: magic, sequence length (u32), sentinel row (u32), then the BWT packed into u64
+    /// words of 32 rows each. All integers little endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = FM_INDEX_MAGIC.to_vec();
+        bytes.extend_from_slice(&(self.length as u32).to_le_bytes());
+        bytes.extend_from_slice(&(self.sentinel_row as u32).to_le_bytes());
+        for word in &self.bwt {
+            bytes.extend_from_slice(&word.to_le_bytes());
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GenomeError> {
+        let header_length = FM_INDEX_MAGIC.len() + 8;
+        if bytes.len() < header_length || &bytes[..FM_INDEX_MAGIC.len()] != FM_INDEX_MAGIC {
+            return Err(invalid("missing index header"));
+        }
+
+        let length = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        let sentinel_row = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+        let words = &bytes[header_length..];
+        if words.len() != (length + 1).div_ceil(SYMBOLS_PER_WORD) * 8 {
+            return Err(invalid("index length doesn't match the sequence length"));
+        }
+
+        let bwt = words
+            .chunks_exact(8)
+            .map(|word| u64::from_le_bytes(word.try_into().unwrap()))
+            .collect();
+        Self::from_bwt(length, bwt, sentinel_row)
+    }
+
+    /// Restores the counts and suffix samples by walking the whole BWT backwards, which also
+    /// checks that it is the transform of a single sequence.
+    fn from_bwt(length: usize, bwt: Vec<u64>, sentinel_row: usize) -> Result<Self, GenomeError> {
+        let rows = length + 1;
+        if sentinel_row >= rows {
+            return Err(invalid("sentinel row out of range"));
+        }
+        if get_symbol(&bwt, sentinel_row) != Nucleotide::A as u8 {
+            return Err(invalid("sentinel is not stored as an A"));
+        }
+
+        let mut occ_samples = Vec::with_capacity(rows / OCC_SAMPLE_RATE + 1);
+        let mut counts = [0u32; 4];
+        for row in 0..rows {
+            if row % OCC_SAMPLE_RATE == 0 {
+                occ_samples.push(counts);
+            }
+            if row != sentinel_row {
+                counts[get_symbol(&bwt, row) as usize] += 1;
+            }
+        }
+        occ_samples.push(counts);
+
+        let mut first_rows = [1usize; 4];
+        for symbol in 1..4 {
+            first_rows[symbol] = first_rows[symbol - 1] + counts[symbol - 1] as usize;
+        }
+
+        let mut index = Self {
+            length,
+            bwt,
+            sentinel_row,
+            first_rows,
+            occ_samples,
+            sampled_rows: vec![0; rows.div_ceil(64)],
+            sampled_ranks: Vec::new(),
+            sa_samples: Vec::new(),
+        };
+
+        // Row 0 is the suffix of the sentinel alone, every step moves one position to the left
+        let mut samples = Vec::with_capacity(rows / SA_SAMPLE_RATE + 1);
+        let mut row = 0;
+        for suffix in (0..rows).rev() {
+            if suffix % SA_SAMPLE_RATE == 0 {
+                samples.push((row, suffix as u32));
+            }
+            if suffix == 0 {
+                break;
+            }
+            if row == index.sentinel_row {
+                return Err(invalid("not the transform of a single sequence"));
+            }
+            row = index.last_to_first(row);
+        }
+        if row != index.sentinel_row {
+            return Err(invalid("not the transform of a single sequence"));
+        }
+
+        samples.sort_unstable();
+        for (row, _) in &samples {
+            index.sampled_rows[row / 64] |= 1 << (row % 64);
+        }
+        let mut rank = 0;
+        for word in &index.sampled_rows {
+            index.sampled_ranks.push(rank);
+            rank += word.count_ones();
+        }
+        index.sa_samples = samples.into_iter().map(|(_, suffix)| suffix).collect();
+
+        Ok(index)
+    }
+
+    /// Rows of the suffixes starting with the query, as `(start, end)`.
+    fn find_rows(&self, query: &[Nucleotide]) -> Option<(usize, usize)> {
+        if query.is_empty() {
+            return None;
+        }
+
+        let (mut start, mut end) = (0, self.length + 1);
+        for nucleotide in query.iter().rev() {
+            let symbol = *nucleotide as usize;
+            start = self.first_rows[symbol] + self.occ(symbol, start);
+            end = self.first_rows[symbol] + self.occ(symbol, end);
+            if start >= end {
+                return None;
+            }
+        }
+        Some((start, end))
+    }
+
+    /// Occurrences of the nucleotide in the BWT rows before `row`.
+    fn occ(&self, symbol: usize, row: usize) -> usize {
+        let block_start = row / OCC_SAMPLE_RATE * OCC_SAMPLE_RATE;
+        let mut count = self.occ_samples[row / OCC_SAMPLE_RATE][symbol] as usize;
+
+        let mut position = block_start;
+        while position < row {
+            let word = self.bwt[position / SYMBOLS_PER_WORD];
+            let symbols = (row - position).min(SYMBOLS_PER_WORD);
+            count += count_in_word(word, symbol as u64, symbols);
+            position += symbols;
+        }
+
+        // The sentinel is stored as an `A`
+        if symbol == Nucleotide::A as usize && (block_start..row).contains(&self.sentinel_row) {
+            count -= 1;
+        }
+        count
+    }
+
+    /// Row of the suffix one position to the left of the suffix in the given row.
+    fn last_to_first(&self, row: usize) -> usize {
+        let symbol = get_symbol(&self.bwt, row) as usize;
+        self.first_rows[symbol] + self.occ(symbol, row)
+    }
+
+    fn suffix_at(&self, row: usize) -> usize {
+        let mut row = row;
+        let mut steps = 0;
+        while self.sampled_rows[row / 64] & (1 << (row % 64)) == 0 {
+            row = self.last_to_first(row);
+            steps += 1;
+        }
+
+        let word = self.sampled_rows[row / 64] & ((1 << (row % 64)) - 1);
+        let rank = self.sampled_ranks[row / 64] + word.count_ones();
+        self.sa_samples[rank as usize] as usize + steps
+    }
+}
+
+impl Serialize for FmIndex {
+    /// Base64 with [`BASE64_PREFIX`] for human readable formats, bytes otherwise.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let encoded = STANDARD.encode(self.to_bytes());
+            serializer.serialize_str(&format!("{}{}", BASE64_PREFIX, encoded))
+        } else {
+            serializer.serialize_bytes(&self.to_bytes())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for FmIndex {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(FmIndexVisitor)
+        } else {
+            deserializer.deserialize_bytes(FmIndexVisitor)
+        }
+    }
+}
+
+struct FmIndexVisitor;
+
+impl<'de> Visitor<'de> for FmIndexVisitor {
+    type Value = FmIndex;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("a base64 string or bytes of an FM-index")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        let encoded = value
+            .strip_prefix(BASE64_PREFIX)
+            .ok_or_else(|| E::custom(format!("expected a {} string", BASE64_PREFIX)))?;
+        let bytes = STANDARD.decode(encoded.trim()).map_err(E::custom)?;
+        self.visit_bytes(&bytes)
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        FmIndex::from_bytes(value).map_err(E::custom)
+    }
+}
+
+/// FM-indices of all chromosomes of a genome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GenomeIndex {
+    chromosomes: Vec<(String, FmIndex)>,
+}
+
+impl GenomeIndex {
+    pub fn new(genome: &Genome) -> Self {
+        Self {
+            chromosomes: genome
+                .get_chromosomes()
+                .iter()
+                .map(|chromosome| {
+                    (
+                        chromosome.get_name().to_string(),
+                        FmIndex::new(chromosome.get_sequence()),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    pub fn get_index(&self, chromosome: &str) -> Option<&FmIndex> {
+        self.chromosomes
+            .iter()
+            .find(|(name, _)| name == chromosome)
+            .map(|(_, index)| index)
+    }
+
+    pub fn count(&self, query: &[Nucleotide]) -> usize {
+        self.chromosomes
+            .iter()
+            .map(|(_, index)| index.count(query))
+            .sum()
+    }
+
+    /// Chromosome and start of every occurrence, in chromosome order.
+    pub fn locate(&self, query: &[Nucleotide]) -> Vec<(&str, usize)> {
+        self.chromosomes
+            .iter()
+            .flat_map(|(name, index)| {
+                index
+                    .locate(query)
+                    .into_iter()
+                    .map(move |position| (name.as_str(), position))
+            })
+            .collect()
+    }
+
+    /// Layout: magic, chromosome count (u32), then per chromosome the name length (u16), the
+    /// UTF-8 name, the index length (u32) and the index (see [`FmIndex::to_bytes`]).
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = GENOME_INDEX_MAGIC.to_vec();
+        bytes.extend_from_slice(&(self.chromosomes.len() as u32).to_le_bytes());
+        for (name, index) in &self.chromosomes {
+            bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(name.as_bytes());
+
+            let index = index.to_bytes();
+            bytes.extend_from_slice(&(index.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&index);
+        }
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GenomeError> {
+        let mut position = 0;
+        let mut take = |length: usize| {
+            let slice = bytes
+                .get(position..position + length)
+                .ok_or_else(|| invalid("unexpected end of data"))?;
+            position += length;
+            Ok::<_, GenomeError>(slice)
+        };
+
+        if take(GENOME_INDEX_MAGIC.len())? != GENOME_INDEX_MAGIC {
+            return Err(invalid("missing genome index header"));
+        }
+        let count = u32::from_le_bytes(take(4)?.try_into().unwrap());
+        let mut chromosomes = Vec::new();
+        for _ in 0..count {
+            let name_length = u16::from_le_bytes(take(2)?.try_into().unwrap()) as usize;
+            let name = String::from_utf8(take(name_length)?.to_vec())
+                .map_err(|_| invalid("chromosome name is not valid UTF-8"))?;
+            let index_length = u32::from_le_bytes(take(4)?.try_into().unwrap()) as usize;
+            chromosomes.push((name, FmIndex::from_bytes(take(index_length)?)?));
+        }
+
+        Ok(Self { chromosomes })
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), GenomeError> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, GenomeError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+fn invalid(reason: &str) -> GenomeError {
+    GenomeError::InvalidBytes(reason.to_string())
+}
+
+/// Suffix array by prefix doubling, the text has to end with a unique smallest symbol.
+fn suffix_array(text: &[u8]) -> Vec<u32> {
+    let length = text.len();
+    let mut suffixes: Vec<u32> = (0..length as u32).collect();
+    let mut ranks: Vec<u32> = text.iter().map(|symbol| *symbol as u32).collect();
+    let mut next_ranks = vec![0u32; length];
+    let mut offset = 1;
+
+    loop {
+        {
+            // Rank of the first `offset` symbols and of the following ones, 0 past the end
+            let key = |suffix: u32| {
+                let suffix = suffix as usize;
+                let following = ranks.get(suffix + offset).map_or(0, |rank| rank + 1);
+                (ranks[suffix], following)
+            };
+            suffixes.sort_unstable_by_key(|suffix| key(*suffix));
+
+            next_ranks[suffixes[0] as usize] = 0;
+            for pair in suffixes.windows(2) {
+                let previous = next_ranks[pair[0] as usize];
+                next_ranks[pair[1] as usize] = previous + (key(pair[0]) != key(pair[1])) as u32;
+            }
+        }
+        std::mem::swap(&mut ranks, &mut next_ranks);
+
+        if ranks[suffixes[length - 1] as usize] as usize == length - 1 {
+            return suffixes;
+        }
+        offset *= 2;
+    }
+}
+
+fn get_symbol(words: &[u64], row: usize) -> u8 {
+    ((words[row / SYMBOLS_PER_WORD] >> (2 * (row % SYMBOLS_PER_WORD))) & 0b11) as u8
+}
+
+fn set_symbol(words: &mut [u64], row: usize, symbol: u8) {
+    words[row / SYMBOLS_PER_WORD] |= (symbol as u64) << (2 * (row % SYMBOLS_PER_WORD));
+}
+
+/// Occurrences of the symbol in the first symbols of a packed word.
+fn count_in_word(word: u64, symbol: u64, symbols: usize) -> usize {
+    let difference = word ^ (symbol * LOW_BITS);
+    let mut matches = !(difference | (difference >> 1)) & LOW_BITS;
+    if symbols < SYMBOLS_PER_WORD {
+        matches &= (1 << (2 * symbols)) - 1;
+    }
+    matches.count_ones() as usize
+}
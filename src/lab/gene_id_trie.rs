@@ -0,0 +1,164 @@
+use crate::lab::types::codon::{Codon, CodonType};
+use crate::lab::types::gene_type::GeneTypeTrait;
+use std::collections::{BTreeMap, VecDeque};
+
+const ROOT: usize = 0;
+
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: BTreeMap<CodonType, usize>,
+    depth: usize,
+    failure: usize,
+    /// Index of the gene type whose id sequence ends at this node.
+    output: Option<usize>,
+    /// Closest node along the failure links which has an output.
+    dictionary_suffix: Option<usize>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct GeneIdMatch<'a> {
+    pub position: usize,
+    pub length: usize,
+    pub gene_type: &'a dyn GeneTypeTrait,
+}
+
+/// Aho-Corasick automaton over the id sequences of a set of gene types.
+#[derive(Debug)]
+pub struct GeneIdTrie {
+    nodes: Vec<TrieNode>,
+    gene_types: Vec<Box<dyn GeneTypeTrait>>,
+}
+
+impl GeneIdTrie {
+    pub fn new(gene_types: Vec<Box<dyn GeneTypeTrait>>) -> Self {
+        let mut trie = Self {
+            nodes: vec![TrieNode::default()],
+            gene_types,
+        };
+
+        for index in 0..trie.gene_types.len() {
+            trie.insert(index);
+        }
+        trie.build_failure_links();
+
+        trie
+    }
+
+    fn insert(&mut self, gene_type_index: usize) {
+        let mut node = ROOT;
+        for codon_type in self.gene_types[gene_type_index].id_sequence() {
+            node = match self.nodes[node].children.get(&codon_type) {
+                Some(&child) => child,
+                None => {
+                    let child = self.nodes.len();
+                    self.nodes.push(TrieNode {
+                        depth: self.nodes[node].depth + 1,
+                        ..Default::default()
+                    });
+                    self.nodes[node].children.insert(codon_type, child);
+                    child
+                }
+            };
+        }
+
+        // An empty id sequence would match everywhere, so it is never reported
+        if node != ROOT {
+            self.nodes[node].output = Some(gene_type_index);
+        }
+    }
+
+    fn build_failure_links(&mut self) {
+        let mut queue: VecDeque<usize> = self.nodes[ROOT].children.values().copied().collect();
+
+        while let Some(node) = queue.pop_front() {
+            let children: Vec<(CodonType, usize)> = self.nodes[node]
+                .children
+                .iter()
+                .map(|(codon_type, child)| (*codon_type, *child))
+                .collect();
+
+            for (codon_type, child) in children {
+                let mut fallback = self.nodes[node].failure;
+                let failure = loop {
+                    match self.nodes[fallback].children.get(&codon_type) {
+                        Some(&target) if target != child => break target,
+                        _ if fallback == ROOT => break ROOT,
+                        _ => fallback = self.nodes[fallback].failure,
+                    }
+                };
+
+                self.nodes[child].failure = failure;
+                self.nodes[child].dictionary_suffix = if self.nodes[failure].output.is_some() {
+                    Some(failure)
+                } else {
+                    self.nodes[failure].dictionary_suffix
+                };
+                queue.push_back(child);
+            }
+        }
+    }
+
+    fn step(&self, mut node: usize, codon_type: CodonType) -> usize {
+        loop {
+            if let Some(&child) = self.nodes[node].children.get(&codon_type) {
+                return child;
+            }
+            if node == ROOT {
+                return ROOT;
+            }
+            node = self.nodes[node].failure;
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.gene_types.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.gene_types.is_empty()
+    }
+
+    /// Returns the gene type whose id sequence the given codons start with, together with the
+    /// amount of codons the id sequence consumed.
+    pub fn find_prefix(&self, codons: &[Codon]) -> Option<(&dyn GeneTypeTrait, usize)> {
+        let mut node = ROOT;
+        for codon in codons {
+            node = *self.nodes[node].children.get(&codon.get_type())?;
+            if let Some(index) = self.nodes[node].output {
+                return Some((self.gene_types[index].as_ref(), self.nodes[node].depth));
+            }
+        }
+        None
+    }
+
+    /// Scans the whole codon sequence in a single pass and returns every occurrence of any id
+    /// sequence, ordered by the position at which the occurrence ends.
+    pub fn find_all(&self, codons: &[Codon]) -> Vec<GeneIdMatch<'_>> {
+        let mut matches = Vec::new();
+        let mut node = ROOT;
+
+        for (i, codon) in codons.iter().enumerate() {
+            node = self.step(node, codon.get_type());
+
+            let mut output_node = if self.nodes[node].output.is_some() {
+                Some(node)
+            } else {
+                self.nodes[node].dictionary_suffix
+            };
+
+            while let Some(current) = output_node {
+                let current_node = &self.nodes[current];
+                if let Some(index) = current_node.output {
+                    matches.push(GeneIdMatch {
+                        position: i + 1 - current_node.depth,
+                        length: current_node.depth,
+                        gene_type: self.gene_types[index].as_ref(),
+                    });
+                }
+                output_node = current_node.dictionary_suffix;
+            }
+        }
+
+        matches
+    }
+}
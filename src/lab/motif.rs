@@ -0,0 +1,471 @@
+use crate::lab::diff::reverse_complement;
+use crate::lab::error::MotifError;
+use crate::lab::types::codon::CodonType;
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::nucleotide::Nucleotide;
+use std::ops::Range;
+
+const ANY_NUCLEOTIDE: u32 = 0b1111;
+const ANY_CODON_TYPE: u32 = (1 << 21) - 1;
+const NO_MATCH: usize = usize::MAX;
+/// Largest repeat count, every repeated item is a state of the matcher.
+const MAX_REPEAT: usize = 10_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Strand {
+    Forward,
+    /// The reverse complement, match positions still refer to the forward strand.
+    Reverse,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MotifMatch {
+    pub start: usize,
+    pub end: usize,
+    pub strand: Strand,
+    /// Score of position weight matrix matches.
+    pub score: Option<f64>,
+}
+
+/// An element of a pattern, matching `min` to `max` consecutive items of the allowed set.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct PatternElement {
+    /// Bit mask of the allowed items.
+    allowed: u32,
+    min: usize,
+    max: usize,
+}
+
+/// A pattern over nucleotides.
+///
+/// Patterns are written with IUPAC codes (`ACGT`, `U` as `T`, `RYSWKMBDHVN`) and `.` for any
+/// nucleotide, `[...]` for a set of codes and `{n}` or `{min,max}` to repeat the previous element,
+/// e.g. `TATA[AT]A.{2,6}GG`. Whitespace and case are ignored.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NucleotidePattern {
+    elements: Vec<PatternElement>,
+}
+
+impl NucleotidePattern {
+    pub fn exact(nucleotides: &[Nucleotide]) -> Self {
+        Self {
+            elements: nucleotides
+                .iter()
+                .map(|nucleotide| PatternElement {
+                    allowed: nucleotide_bit(*nucleotide),
+                    min: 1,
+                    max: 1,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn parse(pattern: &str) -> Result<Self, MotifError> {
+        Ok(Self {
+            elements: parse_elements(pattern, '.', ANY_NUCLEOTIDE, iupac_mask)?,
+        })
+    }
+
+    pub fn min_length(&self) -> usize {
+        self.elements.iter().map(|element| element.min).sum()
+    }
+
+    /// Start and end of the matches on the forward strand. Every position starts at most one
+    /// match, the shortest non-empty one.
+    pub fn find(&self, nucleotides: &[Nucleotide]) -> Vec<Range<usize>> {
+        find_elements(&self.elements, nucleotides, |nucleotide| {
+            nucleotide_bit(*nucleotide)
+        })
+    }
+}
+
+/// A pattern over the amino acids of codons, like [`NucleotidePattern`] with one letter amino
+/// acid codes, `*` for stop codons and `x` or `.` for any codon, e.g. `M[KR]x{2,4}*`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CodonPattern {
+    elements: Vec<PatternElement>,
+}
+
+impl CodonPattern {
+    pub fn exact(codon_types: &[CodonType]) -> Self {
+        Self {
+            elements: codon_types
+                .iter()
+                .map(|codon_type| PatternElement {
+                    allowed: codon_type_bit(*codon_type),
+                    min: 1,
+                    max: 1,
+                })
+                .collect(),
+        }
+    }
+
+    pub fn parse(pattern: &str) -> Result<Self, MotifError> {
+        let mask = |symbol: char| match symbol {
+            'x' | 'X' => Some(ANY_CODON_TYPE),
+            symbol => CodonType::from_symbol(symbol).map(codon_type_bit),
+        };
+        Ok(Self {
+            elements: parse_elements(pattern, '.', ANY_CODON_TYPE, mask)?,
+        })
+    }
+
+    /// Codon index ranges of the matches in the codon types.
+    pub fn find(&self, codon_types: &[CodonType]) -> Vec<Range<usize>> {
+        find_elements(&self.elements, codon_types, |codon_type| {
+            codon_type_bit(*codon_type)
+        })
+    }
+
+    pub fn find_in_codons(&self, codons: &CodonSequence) -> Vec<Range<usize>> {
+        let codon_types: Vec<CodonType> = codons
+            .get_sequence()
+            .iter()
+            .map(|codon| codon.get_type())
+            .collect();
+        self.find(&codon_types)
+    }
+}
+
+/// Log-odds scores of every nucleotide per motif position, in the order A, T, G, C.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PositionWeightMatrix {
+    weights: Vec<[f64; 4]>,
+}
+
+impl PositionWeightMatrix {
+    pub fn new(weights: Vec<[f64; 4]>) -> Result<Self, MotifError> {
+        if weights.is_empty() {
+            return Err(MotifError::InvalidMatrix("no positions".to_string()));
+        }
+        if weights.iter().flatten().any(|weight| !weight.is_finite()) {
+            return Err(MotifError::InvalidMatrix(
+                "weights must be finite".to_string(),
+            ));
+        }
+        Ok(Self { weights })
+    }
+
+    /// Log2 odds against a uniform background from nucleotide counts per position. The
+    /// pseudocount is added to every count so unseen nucleotides don't score minus infinity.
+    pub fn from_counts(counts: &[[f64; 4]], pseudocount: f64) -> Result<Self, MotifError> {
+        let weights = counts
+            .iter()
+            .map(|position| {
+                let total: f64 = position.iter().sum::<f64>() + 4.0 * pseudocount;
+                position.map(|count| ((count + pseudocount) / total / 0.25).log2())
+            })
+            .collect();
+        Self::new(weights)
+    }
+
+    /// Counts the nucleotides of aligned sites of the same length, see [`Self::from_counts`].
+    pub fn from_sites(sites: &[&[Nucleotide]], pseudocount: f64) -> Result<Self, MotifError> {
+        let length = sites.first().map(|site| site.len()).unwrap_or_default();
+        if sites.iter().any(|site| site.len() != length) {
+            return Err(MotifError::InvalidMatrix(
+                "sites differ in length".to_string(),
+            ));
+        }
+
+        let mut counts = vec![[0.0; 4]; length];
+        for site in sites {
+            for (position, nucleotide) in site.iter().enumerate() {
+                counts[position][*nucleotide as usize] += 1.0;
+            }
+        }
+        Self::from_counts(&counts, pseudocount)
+    }
+
+    pub fn get_weights(&self) -> &[[f64; 4]] {
+        &self.weights
+    }
+
+    pub fn len(&self) -> usize {
+        self.weights.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weights.is_empty()
+    }
+
+    /// Score of a window of the motif length.
+    pub fn score(&self, window: &[Nucleotide]) -> f64 {
+        self.weights
+            .iter()
+            .zip(window)
+            .map(|(weights, nucleotide)| weights[*nucleotide as usize])
+            .sum()
+    }
+
+    pub fn max_score(&self) -> f64 {
+        self.weights
+            .iter()
+            .map(|weights| weights.iter().copied().fold(f64::MIN, f64::max))
+            .sum()
+    }
+
+    pub fn min_score(&self) -> f64 {
+        self.weights
+            .iter()
+            .map(|weights| weights.iter().copied().fold(f64::MAX, f64::min))
+            .sum()
+    }
+
+    /// Absolute threshold at the given fraction (0-1) between the minimum and maximum score.
+    pub fn relative_threshold(&self, fraction: f64) -> f64 {
+        self.min_score() + (self.max_score() - self.min_score()) * fraction
+    }
+
+    /// Start and score of every window scoring at least the threshold.
+    pub fn find(&self, nucleotides: &[Nucleotide], threshold: f64) -> Vec<(usize, f64)> {
+        nucleotides
+            .windows(self.len())
+            .enumerate()
+            .map(|(start, window)| (start, self.score(window)))
+            .filter(|(_, score)| *score >= threshold)
+            .collect()
+    }
+}
+
+/// A nucleotide motif searched on one or both strands.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Motif {
+    Pattern(NucleotidePattern),
+    Matrix {
+        matrix: PositionWeightMatrix,
+        threshold: f64,
+    },
+}
+
+impl Motif {
+    /// Matches on the forward strand.
+    pub fn find(&self, nucleotides: &[Nucleotide]) -> Vec<MotifMatch> {
+        self.find_on_strand(nucleotides, Strand::Forward)
+    }
+
+    /// Matches on the forward strand and the reverse complement, ordered by position.
+    pub fn find_on_both_strands(&self, nucleotides: &[Nucleotide]) -> Vec<MotifMatch> {
+        let reverse = reverse_complement(nucleotides);
+        let mut matches = self.find_on_strand(nucleotides, Strand::Forward);
+        matches.extend(
+            self.find_on_strand(&reverse, Strand::Reverse)
+                .into_iter()
+                .map(|found| MotifMatch {
+                    start: nucleotides.len() - found.end,
+                    end: nucleotides.len() - found.start,
+                    ..found
+                }),
+        );
+        matches.sort_by_key(|found| (found.start, found.strand, found.end));
+        matches
+    }
+
+    fn find_on_strand(&self, nucleotides: &[Nucleotide], strand: Strand) -> Vec<MotifMatch> {
+        match self {
+            Motif::Pattern(pattern) => pattern
+                .find(nucleotides)
+                .into_iter()
+                .map(|range| MotifMatch {
+                    start: range.start,
+                    end: range.end,
+                    strand,
+                    score: None,
+                })
+                .collect(),
+            Motif::Matrix { matrix, threshold } => matrix
+                .find(nucleotides, *threshold)
+                .into_iter()
+                .map(|(start, score)| MotifMatch {
+                    start,
+                    end: start + matrix.len(),
+                    strand,
+                    score: Some(score),
+                })
+                .collect(),
+        }
+    }
+}
+
+fn nucleotide_bit(nucleotide: Nucleotide) -> u32 {
+    1 << nucleotide as u32
+}
+
+fn codon_type_bit(codon_type: CodonType) -> u32 {
+    1 << codon_type as u32
+}
+
+fn iupac_mask(code: char) -> Option<u32> {
+    let [a, t, g, c] =
+        [Nucleotide::A, Nucleotide::T, Nucleotide::G, Nucleotide::C].map(nucleotide_bit);
+    let mask = match code.to_ascii_uppercase() {
+        'A' => a,
+        'T' | 'U' => t,
+        'G' => g,
+        'C' => c,
+        'R' => a | g,
+        'Y' => c | t,
+        'S' => g | c,
+        'W' => a | t,
+        'K' => g | t,
+        'M' => a | c,
+        'B' => c | g | t,
+        'D' => a | g | t,
+        'H' => a | c | t,
+        'V' => a | c | g,
+        'N' => a | t | g | c,
+        _ => return None,
+    };
+    Some(mask)
+}
+
+fn parse_elements(
+    pattern: &str,
+    any: char,
+    any_mask: u32,
+    symbol_mask: impl Fn(char) -> Option<u32>,
+) -> Result<Vec<PatternElement>, MotifError> {
+    let symbols: Vec<(usize, char)> = pattern
+        .chars()
+        .enumerate()
+        .filter(|(_, symbol)| !symbol.is_whitespace())
+        .collect();
+    let error = |position: usize, reason: &str| MotifError::Parse {
+        position,
+        reason: reason.to_string(),
+    };
+
+    let mut elements: Vec<PatternElement> = Vec::new();
+    let mut index = 0;
+    while let Some(&(position, symbol)) = symbols.get(index) {
+        index += 1;
+        let allowed = match symbol {
+            '[' => {
+                let mut allowed = 0;
+                loop {
+                    match symbols.get(index) {
+                        Some((_, ']')) => break,
+                        Some(&(position, symbol)) => {
+                            allowed |= symbol_mask(symbol)
+                                .ok_or_else(|| error(position, "unknown symbol in set"))?;
+                            index += 1;
+                        }
+                        None => return Err(error(position, "unclosed set")),
+                    }
+                }
+                index += 1;
+                if allowed == 0 {
+                    return Err(error(position, "empty set"));
+                }
+                allowed
+            }
+            '{' => {
+                let end = symbols[index..]
+                    .iter()
+                    .position(|(_, symbol)| *symbol == '}')
+                    .ok_or_else(|| error(position, "unclosed repeat"))?;
+                let repeat: String = symbols[index..index + end]
+                    .iter()
+                    .map(|(_, symbol)| symbol)
+                    .collect();
+                index += end + 1;
+
+                let parse = |value: &str| {
+                    value
+                        .parse::<usize>()
+                        .map_err(|_| error(position, "repeat is not a number"))
+                };
+                let (min, max) = match repeat.split_once(',') {
+                    Some((min, max)) => (parse(min)?, parse(max)?),
+                    None => (parse(&repeat)?, parse(&repeat)?),
+                };
+                let Some(element) = elements.last_mut() else {
+                    return Err(error(position, "repeat without element"));
+                };
+                if (element.min, element.max) != (1, 1) {
+                    return Err(error(position, "element is already repeated"));
+                }
+                if min > max {
+                    return Err(error(position, "minimum repeat exceeds the maximum"));
+                }
+                if max > MAX_REPEAT {
+                    return Err(error(position, "repeat is too large"));
+                }
+                (element.min, element.max) = (min, max);
+                continue;
+            }
+            symbol if symbol == any => any_mask,
+            symbol => symbol_mask(symbol).ok_or_else(|| error(position, "unknown symbol"))?,
+        };
+        elements.push(PatternElement {
+            allowed,
+            min: 1,
+            max: 1,
+        });
+    }
+
+    if elements.is_empty() {
+        return Err(error(0, "empty pattern"));
+    }
+    Ok(elements)
+}
+
+/// Shortest non-empty match at every position, computed from the end of the items backwards over
+/// the states of the pattern in O(items × states).
+///
+/// State `offsets[i] + j` means `j` items of element `i` are matched, the last state accepts.
+fn find_elements<T>(
+    elements: &[PatternElement],
+    items: &[T],
+    bit: impl Fn(&T) -> u32,
+) -> Vec<Range<usize>> {
+    let mut offsets = Vec::with_capacity(elements.len() + 1);
+    let mut state_count = 0;
+    for element in elements {
+        offsets.push(state_count);
+        state_count += element.max + 1;
+    }
+    offsets.push(state_count);
+    state_count += 1;
+
+    // End of the shortest match from every state at the current and the next position
+    let mut current = vec![NO_MATCH; state_count];
+    let mut next = vec![NO_MATCH; state_count];
+    let mut matches = Vec::new();
+
+    for position in (0..=items.len()).rev() {
+        let item_bits = items.get(position).map_or(0, &bit);
+        current[state_count - 1] = position;
+        for (index, element) in elements.iter().enumerate().rev() {
+            for count in (0..=element.max).rev() {
+                let state = offsets[index] + count;
+                let mut end = NO_MATCH;
+                if count < element.max && item_bits & element.allowed != 0 {
+                    end = next[state + 1];
+                }
+                if count >= element.min {
+                    end = end.min(current[offsets[index + 1]]);
+                }
+                current[state] = end;
+            }
+        }
+
+        // The first item is matched by the first element or one after optional elements
+        let mut shortest = NO_MATCH;
+        for (index, element) in elements.iter().enumerate() {
+            if element.max > 0 && item_bits & element.allowed != 0 {
+                shortest = shortest.min(next[offsets[index] + 1]);
+            }
+            if element.min > 0 {
+                break;
+            }
+        }
+        if shortest != NO_MATCH {
+            matches.push(position..shortest);
+        }
+        std::mem::swap(&mut current, &mut next);
+    }
+
+    matches.reverse();
+    matches
+}
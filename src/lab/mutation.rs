@@ -0,0 +1,215 @@
+use crate::lab::error::GenomeError;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use rand::seq::IndexedRandom;
+use rand::{Rng, rng};
+
+/// Mutations which change the chromosome structure of a genome.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChromosomeMutation {
+    /// Appends the second chromosome to the first one, the second chromosome is removed.
+    Fusion { first: String, second: String },
+    /// Splits a chromosome at the given position, the tail becomes a new chromosome placed right
+    /// after it.
+    Fission {
+        chromosome: String,
+        position: usize,
+        new_name: String,
+    },
+    /// Cuts a segment out of the source chromosome and inserts it into the target chromosome. The
+    /// target position refers to the target chromosome after the segment was cut out.
+    Translocation {
+        source: String,
+        start: usize,
+        length: usize,
+        target: String,
+        position: usize,
+    },
+}
+
+impl ChromosomeMutation {
+    pub fn apply(&self, genome: &mut Genome) -> Result<(), GenomeError> {
+        match self {
+            ChromosomeMutation::Fusion { first, second } => {
+                get_nucleotides(genome, first)?;
+                if first == second {
+                    return Err(GenomeError::DuplicateChromosome(second.clone()));
+                }
+
+                let tail = genome.remove_chromosome(second)?;
+                let mut nucleotides = get_nucleotides(genome, first)?;
+                nucleotides.extend_from_slice(tail.get_sequence().get_sequence());
+                set_nucleotides(genome, first, nucleotides);
+            }
+            ChromosomeMutation::Fission {
+                chromosome,
+                position,
+                new_name,
+            } => {
+                let mut nucleotides = get_nucleotides(genome, chromosome)?;
+                check_bounds(chromosome, *position, nucleotides.len())?;
+                if genome.get_chromosome(new_name).is_some() {
+                    return Err(GenomeError::DuplicateChromosome(new_name.clone()));
+                }
+
+                let tail = nucleotides.split_off(*position);
+                set_nucleotides(genome, chromosome, nucleotides);
+                let index = genome.index_of(chromosome).unwrap_or_default() + 1;
+                genome.insert_chromosome(
+                    index,
+                    Chromosome::new(new_name.clone(), NucleotideSequence::new(tail)),
+                )?;
+            }
+            ChromosomeMutation::Translocation {
+                source,
+                start,
+                length,
+                target,
+                position,
+            } => {
+                let mut source_nucleotides = get_nucleotides(genome, source)?;
+                get_nucleotides(genome, target)?;
+                // An overflowing end is past any chromosome
+                let end = start.checked_add(*length).unwrap_or(usize::MAX);
+                check_bounds(source, end, source_nucleotides.len())?;
+
+                let segment: Vec<Nucleotide> = source_nucleotides.drain(*start..end).collect();
+                set_nucleotides(genome, source, source_nucleotides);
+
+                let mut target_nucleotides = get_nucleotides(genome, target)?;
+                if let Err(error) = check_bounds(target, *position, target_nucleotides.len()) {
+                    // Put the segment back so a failed mutation leaves the genome untouched
+                    let mut source_nucleotides = get_nucleotides(genome, source)?;
+                    source_nucleotides.splice(*start..*start, segment);
+                    set_nucleotides(genome, source, source_nucleotides);
+                    return Err(error);
+                }
+
+                target_nucleotides.splice(*position..*position, segment);
+                set_nucleotides(genome, target, target_nucleotides);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Picks a random structural mutation which can be applied to the genome, if there is any.
+    pub fn random(genome: &Genome) -> Option<Self> {
+        let mut rng = rng();
+        let chromosomes = genome.get_chromosomes();
+        let chromosome = chromosomes.choose(&mut rng)?;
+        let name = chromosome.get_name().to_string();
+        let length = chromosome.get_sequence().len();
+
+        match rng.random_range(0..3) {
+            0 if chromosomes.len() > 1 => {
+                let second = chromosomes
+                    .iter()
+                    .filter(|other| other.get_name() != name)
+                    .collect::<Vec<_>>()
+                    .choose(&mut rng)?
+                    .get_name()
+                    .to_string();
+                Some(ChromosomeMutation::Fusion {
+                    first: name,
+                    second,
+                })
+            }
+            1 if length > 1 => {
+                let mut suffix = 1;
+                while genome
+                    .get_chromosome(&format!("{}.{}", name, suffix))
+                    .is_some()
+                {
+                    suffix += 1;
+                }
+                Some(ChromosomeMutation::Fission {
+                    position: rng.random_range(1..length),
+                    new_name: format!("{}.{}", name, suffix),
+                    chromosome: name,
+                })
+            }
+            _ if length > 0 => {
+                let start = rng.random_range(0..length);
+                let segment_length = rng.random_range(1..=length - start);
+                let target = chromosomes.choose(&mut rng)?;
+                let target_length = if target.get_name() == name {
+                    length - segment_length
+                } else {
+                    target.get_sequence().len()
+                };
+                Some(ChromosomeMutation::Translocation {
+                    source: name,
+                    start,
+                    length: segment_length,
+                    target: target.get_name().to_string(),
+                    position: rng.random_range(0..=target_length),
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// Replaces every nucleotide with the given probability by a different random one and returns the
+/// amount of substitutions. The rate is clamped to 0..=1, NaN substitutes nothing.
+pub fn substitute_random(genome: &mut Genome, rate: f64) -> usize {
+    let rate = to_probability(rate);
+    let mut rng = rng();
+    let mut substitutions = 0;
+
+    let names: Vec<String> = genome
+        .get_chromosomes()
+        .iter()
+        .map(|chromosome| chromosome.get_name().to_string())
+        .collect();
+    for name in names {
+        let Ok(mut nucleotides) = get_nucleotides(genome, &name) else {
+            continue;
+        };
+        for nucleotide in nucleotides.iter_mut() {
+            if rng.random_bool(rate) {
+                let offset = rng.random_range(1..4u8);
+                *nucleotide = Nucleotide::from(*nucleotide as u8 + offset);
+                substitutions += 1;
+            }
+        }
+        set_nucleotides(genome, &name, nucleotides);
+    }
+
+    substitutions
+}
+
+fn get_nucleotides(genome: &Genome, name: &str) -> Result<Vec<Nucleotide>, GenomeError> {
+    genome
+        .get_chromosome(name)
+        .map(|chromosome| chromosome.get_sequence().get_sequence().to_vec())
+        .ok_or_else(|| GenomeError::UnknownChromosome(name.to_string()))
+}
+
+fn set_nucleotides(genome: &mut Genome, name: &str, nucleotides: Vec<Nucleotide>) {
+    if let Some(chromosome) = genome.get_chromosome_mut(name) {
+        chromosome.set_sequence(NucleotideSequence::new(nucleotides));
+    }
+}
+
+/// Clamps a rate to 0..=1 for sampling, NaN never happens.
+pub(crate) fn to_probability(rate: f64) -> f64 {
+    if rate.is_nan() {
+        0.0
+    } else {
+        rate.clamp(0.0, 1.0)
+    }
+}
+
+fn check_bounds(chromosome: &str, position: usize, length: usize) -> Result<(), GenomeError> {
+    if position > length {
+        return Err(GenomeError::OutOfBounds {
+            chromosome: chromosome.to_string(),
+            position,
+            length,
+        });
+    }
+    Ok(())
+}
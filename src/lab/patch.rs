@@ -0,0 +1,295 @@
+use crate::lab::diff::{SequenceDiff, SequenceEdit};
+use crate::lab::error::PatchError;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+
+/// First line of every patch.
+pub const PATCH_HEADER: &str = "bio-lab patch 1";
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChromosomePatch {
+    Modify {
+        name: String,
+        diff: SequenceDiff,
+    },
+    /// The index is the position of the chromosome in the patched genome.
+    Add {
+        index: usize,
+        chromosome: Chromosome,
+    },
+    Remove(String),
+}
+
+/// Changes turning one genome into another.
+///
+/// The text format starts with [`PATCH_HEADER`], followed by one section per changed chromosome.
+/// Lines starting with `#` are comments.
+///
+/// ```text
+/// @ <chromosome>          edits of a chromosome, positions refer to the original
+/// S <position> <from>><to>  substitution, e.g. `S 12 A>G`
+/// I <position> <ATGC>     insertion before the position
+/// D <position> <ATGC>     deletion of the given nucleotides
+/// V <position> <length>   inversion (reverse complement) of a segment
+/// + <chromosome> <index> <ATGC>  added chromosome at the given index
+/// - <chromosome>          removed chromosome
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct GenomePatch {
+    changes: Vec<ChromosomePatch>,
+}
+
+impl GenomePatch {
+    pub fn new(changes: Vec<ChromosomePatch>) -> Self {
+        Self { changes }
+    }
+
+    pub fn get_changes(&self) -> &[ChromosomePatch] {
+        &self.changes
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Chromosomes are paired by name, unchanged chromosomes are left out.
+    pub fn between(original: &Genome, target: &Genome) -> Self {
+        let mut changes = Vec::new();
+        for chromosome in original.get_chromosomes() {
+            match target.get_chromosome(chromosome.get_name()) {
+                Some(other) => {
+                    let diff =
+                        SequenceDiff::between(chromosome.get_sequence(), other.get_sequence());
+                    if !diff.is_empty() {
+                        changes.push(ChromosomePatch::Modify {
+                            name: chromosome.get_name().to_string(),
+                            diff,
+                        });
+                    }
+                }
+                None => changes.push(ChromosomePatch::Remove(chromosome.get_name().to_string())),
+            }
+        }
+        for (index, chromosome) in target.get_chromosomes().iter().enumerate() {
+            if original.get_chromosome(chromosome.get_name()).is_none() {
+                changes.push(ChromosomePatch::Add {
+                    index,
+                    chromosome: chromosome.clone(),
+                });
+            }
+        }
+        Self::new(changes)
+    }
+
+    /// Applies all changes to a copy of the genome, failing if any of them doesn't fit.
+    pub fn apply(&self, genome: &Genome) -> Result<Genome, PatchError> {
+        let mut patched = genome.clone();
+        for change in &self.changes {
+            match change {
+                ChromosomePatch::Modify { name, diff } => {
+                    let chromosome = patched
+                        .get_chromosome_mut(name)
+                        .ok_or_else(|| PatchError::UnknownChromosome(name.clone()))?;
+                    let sequence = diff.apply(chromosome.get_sequence()).map_err(|conflict| {
+                        PatchError::Conflict {
+                            chromosome: name.clone(),
+                            position: conflict.position,
+                            reason: conflict.reason,
+                        }
+                    })?;
+                    chromosome.set_sequence(sequence);
+                }
+                ChromosomePatch::Add { index, chromosome } => {
+                    patched.insert_chromosome(*index, chromosome.clone())?
+                }
+                ChromosomePatch::Remove(name) => {
+                    patched.remove_chromosome(name)?;
+                }
+            }
+        }
+        Ok(patched)
+    }
+
+    pub fn to_text(&self) -> String {
+        self.render(None)
+    }
+
+    /// Like [`Self::to_text`], with the codon effect of every edit as a comment.
+    pub fn to_annotated_text(&self, original: &Genome) -> String {
+        self.render(Some(original))
+    }
+
+    fn render(&self, original: Option<&Genome>) -> String {
+        let mut lines = vec![PATCH_HEADER.to_string()];
+        for change in &self.changes {
+            match change {
+                ChromosomePatch::Modify { name, diff } => {
+                    lines.push(format!("@ {}", name));
+                    let effects = original
+                        .and_then(|genome| genome.get_chromosome(name))
+                        .map(|chromosome| diff.annotate(chromosome.get_sequence()));
+                    for (index, edit) in diff.get_edits().iter().enumerate() {
+                        let line = edit_line(edit);
+                        match effects.as_ref().and_then(|effects| effects.get(index)) {
+                            Some((_, effect)) => lines.push(format!("{}  # {}", line, effect)),
+                            None => lines.push(line),
+                        }
+                    }
+                }
+                ChromosomePatch::Add { index, chromosome } => lines.push(format!(
+                    "+ {} {} {}",
+                    chromosome.get_name(),
+                    index,
+                    chromosome.get_sequence().get_code_string()
+                )),
+                ChromosomePatch::Remove(name) => lines.push(format!("- {}", name)),
+            }
+        }
+        lines.join("\n") + "\n"
+    }
+
+    pub fn from_text(text: &str) -> Result<Self, PatchError> {
+        let mut lines = text
+            .lines()
+            .enumerate()
+            .map(|(index, line)| (index + 1, strip_comment(line).trim()))
+            .filter(|(_, line)| !line.is_empty());
+
+        match lines.next() {
+            Some((_, PATCH_HEADER)) => {}
+            _ => return Err(parse_error(1, "missing patch header")),
+        }
+
+        let mut changes = Vec::new();
+        // Chromosome and edits of the current `@` section
+        let mut section: Option<(String, Vec<SequenceEdit>)> = None;
+
+        for (number, line) in lines {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let edit = match words.as_slice() {
+                ["@", name] => {
+                    close_section(&mut section, &mut changes);
+                    section = Some((name.to_string(), Vec::new()));
+                    continue;
+                }
+                ["+", name, index, code @ ..] if code.len() <= 1 => {
+                    close_section(&mut section, &mut changes);
+                    let sequence = parse_nucleotides(code.first().unwrap_or(&""), number)?;
+                    changes.push(ChromosomePatch::Add {
+                        index: parse_number(index, number)?,
+                        chromosome: Chromosome::new(*name, NucleotideSequence::new(sequence)),
+                    });
+                    continue;
+                }
+                ["-", name] => {
+                    close_section(&mut section, &mut changes);
+                    changes.push(ChromosomePatch::Remove(name.to_string()));
+                    continue;
+                }
+                ["S", position, change] => {
+                    let (from, to) = change
+                        .split_once('>')
+                        .ok_or_else(|| parse_error(number, "expected <from>><to>"))?;
+                    SequenceEdit::Substitution {
+                        position: parse_number(position, number)?,
+                        from: parse_nucleotide(from, number)?,
+                        to: parse_nucleotide(to, number)?,
+                    }
+                }
+                ["I", position, code] => SequenceEdit::Insertion {
+                    position: parse_number(position, number)?,
+                    nucleotides: parse_nucleotides(code, number)?,
+                },
+                ["D", position, code] => SequenceEdit::Deletion {
+                    position: parse_number(position, number)?,
+                    nucleotides: parse_nucleotides(code, number)?,
+                },
+                ["V", position, length] => SequenceEdit::Inversion {
+                    position: parse_number(position, number)?,
+                    length: parse_number(length, number)?,
+                },
+                _ => return Err(parse_error(number, "unknown line")),
+            };
+
+            match section.as_mut() {
+                Some((_, edits)) => edits.push(edit),
+                None => return Err(parse_error(number, "edit outside of a chromosome section")),
+            }
+        }
+        close_section(&mut section, &mut changes);
+
+        Ok(Self::new(changes))
+    }
+}
+
+fn edit_line(edit: &SequenceEdit) -> String {
+    match edit {
+        SequenceEdit::Substitution { position, from, to } => {
+            format!("S {} {}>{}", position, char::from(*from), char::from(*to))
+        }
+        SequenceEdit::Insertion {
+            position,
+            nucleotides,
+        } => format!("I {} {}", position, code(nucleotides)),
+        SequenceEdit::Deletion {
+            position,
+            nucleotides,
+        } => format!("D {} {}", position, code(nucleotides)),
+        SequenceEdit::Inversion { position, length } => format!("V {} {}", position, length),
+    }
+}
+
+fn code(nucleotides: &[Nucleotide]) -> String {
+    nucleotides
+        .iter()
+        .map(|nucleotide| char::from(*nucleotide))
+        .collect()
+}
+
+fn close_section(
+    section: &mut Option<(String, Vec<SequenceEdit>)>,
+    changes: &mut Vec<ChromosomePatch>,
+) {
+    if let Some((name, edits)) = section.take() {
+        changes.push(ChromosomePatch::Modify {
+            name,
+            diff: SequenceDiff::new(edits),
+        });
+    }
+}
+
+fn strip_comment(line: &str) -> &str {
+    line.split_once('#')
+        .map(|(content, _)| content)
+        .unwrap_or(line)
+}
+
+fn parse_error(line: usize, reason: &str) -> PatchError {
+    PatchError::Parse {
+        line,
+        reason: reason.to_string(),
+    }
+}
+
+fn parse_number(value: &str, line: usize) -> Result<usize, PatchError> {
+    value
+        .parse()
+        .map_err(|_| parse_error(line, &format!("'{}' is not a number", value)))
+}
+
+fn parse_nucleotides(value: &str, line: usize) -> Result<Vec<Nucleotide>, PatchError> {
+    NucleotideSequence::from_code_string(value)
+        .map(|sequence| sequence.get_sequence().to_vec())
+        .map_err(|invalid| parse_error(line, &format!("'{}' is not a nucleotide", invalid)))
+}
+
+fn parse_nucleotide(value: &str, line: usize) -> Result<Nucleotide, PatchError> {
+    match parse_nucleotides(value, line)?.as_slice() {
+        [nucleotide] => Ok(*nucleotide),
+        _ => Err(parse_error(
+            line,
+            &format!("'{}' is not a single nucleotide", value),
+        )),
+    }
+}
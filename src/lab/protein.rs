@@ -0,0 +1,72 @@
+use crate::lab::config::codon_value_config::CodonValueConfig;
+use crate::lab::config::protein_config::ProteinConfig;
+use crate::lab::types::codon::{Codon, CodonType};
+
+/// Mass of the water molecule kept at the ends of a peptide chain.
+const WATER_MASS: f64 = 18.015;
+const HELIX_THRESHOLD: f64 = 1.03;
+const SHEET_THRESHOLD: f64 = 1.05;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SecondaryStructure {
+    Helix,
+    Sheet,
+    Coil,
+}
+
+/// Physical properties of a translated protein, stop codons are not counted as residues.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ProteinProperties {
+    pub length: usize,
+    /// Mean hydrophobicity (GRAVY score with the default table)
+    pub hydrophobicity: f64,
+    /// Net charge
+    pub charge: f64,
+    /// Total mass including the terminal water, 0.0 for an empty protein
+    pub mass: f64,
+    pub helix_propensity: f64,
+    pub sheet_propensity: f64,
+}
+
+impl ProteinProperties {
+    pub fn from_codons(codons: &[Codon], config: &ProteinConfig) -> Self {
+        let residues: Vec<Codon> = codons
+            .iter()
+            .copied()
+            .filter(|codon| codon.get_type() != CodonType::Stop)
+            .collect();
+        if residues.is_empty() {
+            return Self::default();
+        }
+
+        let length = residues.len();
+        let sum = |table: &CodonValueConfig<f64>| {
+            residues
+                .iter()
+                .map(|codon| table.get_value(*codon))
+                .sum::<f64>()
+        };
+
+        Self {
+            length,
+            hydrophobicity: sum(&config.hydrophobicity) / length as f64,
+            charge: sum(&config.charge),
+            mass: sum(&config.mass) + WATER_MASS,
+            helix_propensity: sum(&config.helix_propensity) / length as f64,
+            sheet_propensity: sum(&config.sheet_propensity) / length as f64,
+        }
+    }
+
+    /// Simplified Chou-Fasman style prediction for the protein as a whole.
+    pub fn secondary_structure(&self) -> SecondaryStructure {
+        if self.helix_propensity >= HELIX_THRESHOLD
+            && self.helix_propensity >= self.sheet_propensity
+        {
+            SecondaryStructure::Helix
+        } else if self.sheet_propensity >= SHEET_THRESHOLD {
+            SecondaryStructure::Sheet
+        } else {
+            SecondaryStructure::Coil
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::lab::types::gene::Gene;
+use crate::lab::types::regulation::RegulationKind;
+
+const MAX_ITERATIONS: usize = 1000;
+const TOLERANCE: f32 = 1e-5;
+const MAX_EXPRESSION: f32 = 10.0;
+/// Only part of every update is applied, which keeps negative feedback loops from oscillating forever.
+const DAMPING: f32 = 0.5;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct Edge {
+    regulator: usize,
+    target: usize,
+    kind: RegulationKind,
+    magnitude: f32,
+}
+
+/// Gene regulatory network over a set of genes, linking every regulatory gene to its targets.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegulatoryNetwork {
+    base_strengths: Vec<f32>,
+    edges: Vec<Edge>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SteadyState {
+    pub strengths: Vec<f32>,
+    pub iterations: usize,
+    pub converged: bool,
+}
+
+impl RegulatoryNetwork {
+    pub fn new(genes: &[Gene]) -> Self {
+        let base_strengths = genes.iter().map(|gene| gene.get_strength()).collect();
+
+        let mut edges = Vec::new();
+        for (regulator, gene) in genes.iter().enumerate() {
+            let Some(regulation) = gene.get_regulation() else {
+                continue;
+            };
+
+            for (target, target_gene) in genes.iter().enumerate() {
+                if regulation.target.matches(target_gene) {
+                    edges.push(Edge {
+                        regulator,
+                        target,
+                        kind: regulation.kind,
+                        magnitude: regulation.magnitude,
+                    });
+                }
+            }
+        }
+
+        Self {
+            base_strengths,
+            edges,
+        }
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges.len()
+    }
+
+    fn step(&self, strengths: &[f32]) -> Vec<f32> {
+        let mut promoted = self.base_strengths.clone();
+        let mut factors = vec![1.0f32; strengths.len()];
+
+        for edge in &self.edges {
+            let effect = edge.magnitude * strengths[edge.regulator];
+            match edge.kind {
+                RegulationKind::Promoter => promoted[edge.target] += effect,
+                RegulationKind::Enhancer => factors[edge.target] *= 1.0 + effect,
+                RegulationKind::Repressor => factors[edge.target] *= (1.0 - effect).max(0.0),
+            }
+        }
+
+        promoted
+            .iter()
+            .zip(factors)
+            .map(|(strength, factor)| (strength * factor).clamp(0.0, MAX_EXPRESSION))
+            .collect()
+    }
+
+    /// Iterates the network from the base strengths until no strength changes by more than the
+    /// tolerance anymore, or gives up after a fixed amount of iterations.
+    pub fn solve(&self) -> SteadyState {
+        let mut strengths = self.base_strengths.clone();
+
+        for iteration in 1..=MAX_ITERATIONS {
+            let target = self.step(&strengths);
+            let mut max_change = 0.0f32;
+
+            for (strength, target) in strengths.iter_mut().zip(target) {
+                let change = (target - *strength) * DAMPING;
+                *strength += change;
+                max_change = max_change.max(change.abs());
+            }
+
+            if max_change < TOLERANCE {
+                return SteadyState {
+                    strengths,
+                    iterations: iteration,
+                    converged: true,
+                };
+            }
+        }
+
+        SteadyState {
+            strengths,
+            iterations: MAX_ITERATIONS,
+            converged: false,
+        }
+    }
+
+    /// Replaces the strength of every gene with its steady state expression.
+    pub fn regulate(genes: &mut [Gene]) -> SteadyState {
+        let steady_state = Self::new(genes).solve();
+        for (gene, strength) in genes.iter_mut().zip(&steady_state.strengths) {
+            gene.set_strength(*strength);
+        }
+        steady_state
+    }
+}
@@ -0,0 +1,332 @@
+use crate::lab::Lab;
+use crate::lab::types::codon::{Codon, CodonType};
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::gene::GeneLocation;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide;
+
+pub mod phenotype_svg;
+
+/// Codons per line of the terminal rendering.
+pub const CODONS_PER_LINE: usize = 20;
+
+const RESET: &str = "\x1b[0m";
+const BOLD_UNDERLINE: &str = "\x1b[1;4m";
+
+/// Gene highlights as (256 color terminal background, HTML color), assigned to the gene types of
+/// the lab in alphabetical order.
+const GENE_COLORS: [(u8, &str); 10] = [
+    (24, "#bbdefb"),
+    (22, "#c8e6c9"),
+    (94, "#ffe0b2"),
+    (54, "#e1bee7"),
+    (23, "#b2dfdb"),
+    (88, "#ffcdd2"),
+    (58, "#f0f4c3"),
+    (60, "#d1c4e9"),
+    (17, "#c5cae9"),
+    (52, "#d7ccc8"),
+];
+
+const HTML_STYLE: &str = "\
+body { font-family: sans-serif; margin: 2em; }
+.sequence { display: flex; flex-wrap: wrap; row-gap: 0.5em; font-family: monospace; font-size: 15px; }
+.codon { display: inline-flex; flex-direction: column; align-items: center; padding: 2px 4px; }
+.amino-acid { color: #555; }
+.start, .stop { font-weight: bold; }
+.start { border-left: 3px solid #2e7d32; }
+.stop { border-right: 3px solid #c62828; }
+.remainder { padding: 2px 4px; color: #999; }
+.a { color: #2e7d32; }
+.t { color: #c62828; }
+.g { color: #b8860b; }
+.c { color: #1565c0; }
+.swatch { display: inline-block; width: 1em; height: 1em; margin-right: 0.5em; vertical-align: middle; }";
+
+/// The codons of a chromosome in its first reading frame with the genes found in them.
+///
+/// Genes are read from the chromosome as is, promoters, terminators and splice sites of the lab
+/// config are not applied.
+#[derive(Debug, Clone)]
+pub struct ChromosomeView {
+    name: String,
+    codons: CodonSequence,
+    genes: Vec<GeneLocation>,
+}
+
+impl ChromosomeView {
+    pub fn new(lab: &Lab, chromosome: &Chromosome) -> Self {
+        let codons = CodonSequence::from_nucleotides(chromosome.get_sequence().get_sequence());
+        let genes = lab.locate_genes(codons.get_sequence());
+        Self {
+            name: chromosome.get_name().to_string(),
+            codons,
+            genes,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_codons(&self) -> &CodonSequence {
+        &self.codons
+    }
+
+    pub fn get_genes(&self) -> &[GeneLocation] {
+        &self.genes
+    }
+
+    /// Index of the gene the codon belongs to.
+    pub fn gene_at(&self, codon: usize) -> Option<usize> {
+        self.genes
+            .iter()
+            .position(|location| location.get_codons().contains(&codon))
+    }
+}
+
+/// Renders a genome for review, colored for terminals or as a standalone HTML report.
+///
+/// Nucleotides are color coded, every codon is labeled with its amino acid, genes are highlighted
+/// by gene type and `Met` and stop codons are marked.
+#[derive(Debug, Clone)]
+pub struct GenomeView {
+    chromosomes: Vec<ChromosomeView>,
+    /// Gene type names in the order of their colors.
+    gene_types: Vec<String>,
+}
+
+impl GenomeView {
+    pub fn new(lab: &Lab, genome: &Genome) -> Self {
+        let mut gene_types: Vec<String> = lab
+            .gene_types()
+            .map(|gene_type| gene_type.name().to_string())
+            .collect();
+        gene_types.sort();
+
+        Self {
+            chromosomes: genome
+                .get_chromosomes()
+                .iter()
+                .map(|chromosome| ChromosomeView::new(lab, chromosome))
+                .collect(),
+            gene_types,
+        }
+    }
+
+    pub fn get_chromosomes(&self) -> &[ChromosomeView] {
+        &self.chromosomes
+    }
+
+    /// Lines of [`CODONS_PER_LINE`] codons prefixed by the position of the first nucleotide, each
+    /// followed by the amino acids. Every chromosome ends with a legend of its genes.
+    pub fn to_ansi(&self) -> String {
+        let mut lines = Vec::new();
+        for chromosome in &self.chromosomes {
+            lines.push(format!("{}{}{}", BOLD_UNDERLINE, chromosome.name, RESET));
+
+            let codons = chromosome.codons.get_sequence();
+            let width = (codons.len() * 3).max(1).to_string().len();
+            for line_start in (0..codons.len()).step_by(CODONS_PER_LINE) {
+                let line_end = (line_start + CODONS_PER_LINE).min(codons.len());
+                let mut sequence = format!("{:>width$}  ", line_start * 3);
+                let mut amino_acids = " ".repeat(width + 2);
+
+                for (index, codon) in codons.iter().enumerate().take(line_end).skip(line_start) {
+                    let gene = chromosome.gene_at(index);
+                    let background = gene.map(|gene| self.ansi_background(chromosome, gene));
+                    let marker = if is_marked(codon) { BOLD_UNDERLINE } else { "" };
+
+                    sequence += background.as_deref().unwrap_or_default();
+                    sequence += marker;
+                    for nucleotide in codon.to_nucleotides() {
+                        sequence += &ansi_nucleotide(nucleotide);
+                    }
+                    sequence += RESET;
+                    // The highlight continues between codons of the same gene
+                    match background {
+                        Some(background) if gene == chromosome.gene_at(index + 1) => {
+                            sequence += &format!("{} {}", background, RESET)
+                        }
+                        _ => sequence.push(' '),
+                    }
+
+                    let symbol = codon.get_type().get_symbol();
+                    amino_acids += &match marker {
+                        "" => format!(" {}  ", symbol),
+                        marker => format!(" {}{}{}  ", marker, symbol, RESET),
+                    };
+                }
+                lines.push(sequence.trim_end().to_string());
+                lines.push(amino_acids.trim_end().to_string());
+            }
+
+            let remainder = chromosome.codons.get_remainder();
+            if !remainder.is_empty() {
+                lines.push(format!(
+                    "{:>width$}  {}",
+                    codons.len() * 3,
+                    nucleotides_ansi(remainder)
+                ));
+            }
+
+            for (index, location) in chromosome.genes.iter().enumerate() {
+                lines.push(format!(
+                    "{}   {} {}",
+                    self.ansi_background(chromosome, index),
+                    RESET,
+                    gene_description(location)
+                ));
+            }
+            lines.push(String::new());
+        }
+        lines.join("\n")
+    }
+
+    /// A complete HTML document, hovering a codon shows its position and gene.
+    pub fn to_html(&self, title: &str) -> String {
+        let mut style = HTML_STYLE.to_string();
+        for (index, (_, color)) in GENE_COLORS.iter().enumerate() {
+            style += &format!("\n.gene-{} {{ background: {}; }}", index, color);
+        }
+
+        let mut html = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>{title}</title>\n\
+             <style>\n{style}\n</style>\n</head>\n<body>\n<h1>{title}</h1>\n",
+            title = escape_html(title),
+            style = style
+        );
+
+        for chromosome in &self.chromosomes {
+            html += &format!(
+                "<section>\n<h2>{}</h2>\n<div class=\"sequence\">\n",
+                escape_html(&chromosome.name)
+            );
+
+            for (index, codon) in chromosome.codons.get_sequence().iter().enumerate() {
+                let gene = chromosome.gene_at(index);
+                let mut classes = vec!["codon".to_string()];
+                let mut title = format!("{}: {:?}", index * 3, codon.get_type());
+                if let Some(gene) = gene {
+                    classes.push(format!("gene-{}", self.color_index(chromosome, gene)));
+                    title += &format!(
+                        ", {} gene",
+                        chromosome.genes[gene].get_gene().get_gene_type().name()
+                    );
+                }
+                match codon.get_type() {
+                    CodonType::Met => classes.push("start".to_string()),
+                    CodonType::Stop => classes.push("stop".to_string()),
+                    _ => {}
+                }
+
+                let nucleotides: String = codon
+                    .to_nucleotides()
+                    .into_iter()
+                    .map(html_nucleotide)
+                    .collect();
+                html += &format!(
+                    "<span class=\"{}\" title=\"{}\"><span>{}</span>\
+                     <span class=\"amino-acid\">{}</span></span>\n",
+                    classes.join(" "),
+                    escape_html(&title),
+                    nucleotides,
+                    codon.get_type().get_symbol()
+                );
+            }
+
+            let remainder: String = chromosome
+                .codons
+                .get_remainder()
+                .iter()
+                .map(|nucleotide| html_nucleotide(*nucleotide))
+                .collect();
+            if !remainder.is_empty() {
+                html += &format!("<span class=\"remainder\">{}</span>\n", remainder);
+            }
+            html += "</div>\n";
+
+            if !chromosome.genes.is_empty() {
+                html += "<ul class=\"genes\">\n";
+                for (index, location) in chromosome.genes.iter().enumerate() {
+                    html += &format!(
+                        "<li><span class=\"swatch gene-{}\"></span>{}</li>\n",
+                        self.color_index(chromosome, index),
+                        escape_html(&gene_description(location))
+                    );
+                }
+                html += "</ul>\n";
+            }
+            html += "</section>\n";
+        }
+
+        html + "</body>\n</html>\n"
+    }
+
+    fn color_index(&self, chromosome: &ChromosomeView, gene: usize) -> usize {
+        let name = chromosome.genes[gene].get_gene().get_gene_type().name();
+        let index = self
+            .gene_types
+            .iter()
+            .position(|gene_type| gene_type == name)
+            .unwrap_or_default();
+        index % GENE_COLORS.len()
+    }
+
+    fn ansi_background(&self, chromosome: &ChromosomeView, gene: usize) -> String {
+        format!(
+            "\x1b[48;5;{}m",
+            GENE_COLORS[self.color_index(chromosome, gene)].0
+        )
+    }
+}
+
+/// Nucleotides colored by base: A green, T red, G yellow, C blue.
+pub fn nucleotides_ansi(nucleotides: &[Nucleotide]) -> String {
+    let colored: String = nucleotides
+        .iter()
+        .map(|nucleotide| ansi_nucleotide(*nucleotide))
+        .collect();
+    colored + RESET
+}
+
+fn ansi_nucleotide(nucleotide: Nucleotide) -> String {
+    let color = match nucleotide {
+        Nucleotide::A => 32,
+        Nucleotide::T => 31,
+        Nucleotide::G => 33,
+        Nucleotide::C => 34,
+    };
+    format!("\x1b[{}m{}", color, char::from(nucleotide))
+}
+
+fn html_nucleotide(nucleotide: Nucleotide) -> String {
+    let code = char::from(nucleotide);
+    format!(
+        "<span class=\"{}\">{}</span>",
+        code.to_ascii_lowercase(),
+        code
+    )
+}
+
+fn is_marked(codon: &Codon) -> bool {
+    matches!(codon.get_type(), CodonType::Met | CodonType::Stop)
+}
+
+fn gene_description(location: &GeneLocation) -> String {
+    let codons = location.get_codons();
+    format!(
+        "{} node {}, nucleotides {}-{}",
+        location.get_gene().get_gene_type().name(),
+        location.get_gene().get_node_id(),
+        codons.start * 3,
+        codons.end * 3
+    )
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,276 @@
+use crate::lab::types::phenotype::{Phenotype, PhenotypeNode};
+use std::f64::consts::TAU;
+
+/// Points sampled along the outline of a node.
+const OUTLINE_POINTS: usize = 48;
+const MARGIN: f64 = 20.0;
+/// Space between neighbouring nodes along the body.
+const NODE_GAP: f64 = 6.0;
+
+/// Draws an expressed entity as a standalone SVG image.
+///
+/// The nodes form a body from left to right in `node_id` order, facing right. Every node is drawn
+/// from the properties its genes expressed:
+///
+/// - `hue`, `saturation`, `lightness`: fill color, grey if missing
+/// - `size`: radius of the node
+/// - `segments`, `symmetry`, `elongation`: lobes of the outline, bilateral (egg shaped) to radial,
+///   width to height ratio
+/// - `speed`: motion lines behind the node
+/// - `efficiency`: size of the darker core
+/// - `range`: dashed sensor circle
+/// - `aggression`: spikes on the outline
+/// - `sociability`: thickness of the link to the next node
+/// - `curiosity`: size of the eye at the front
+pub fn phenotype_svg(phenotype: &Phenotype) -> String {
+    let mut x = MARGIN;
+    let mut nodes = Vec::new();
+    for (node_id, node) in phenotype.get_nodes() {
+        let drawing = NodeDrawing::new(*node_id, node);
+        let (half_width, _) = drawing.get_extent();
+        x += half_width;
+        nodes.push((x, drawing));
+        x += half_width + NODE_GAP;
+    }
+
+    if nodes.is_empty() {
+        return format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" \
+             viewBox=\"0 0 {w} {h}\">\n<text x=\"{m}\" y=\"{m}\" font-family=\"sans-serif\" \
+             font-size=\"12\">no expressed nodes</text>\n</svg>\n",
+            w = 200,
+            h = 40,
+            m = MARGIN
+        );
+    }
+
+    let half_height = nodes
+        .iter()
+        .map(|(_, drawing)| drawing.get_extent().1)
+        .fold(0.0, f64::max);
+    let center_y = MARGIN + half_height;
+    let width = x - NODE_GAP + MARGIN;
+    // Room for the node labels below the body
+    let height = 2.0 * (MARGIN + half_height) + 12.0;
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.0}\" height=\"{h:.0}\" \
+         viewBox=\"0 0 {w:.1} {h:.1}\">\n",
+        w = width,
+        h = height
+    );
+
+    // Sensors and links below the bodies
+    for (x, drawing) in &nodes {
+        svg += &drawing.sensor(*x, center_y);
+    }
+    for pair in nodes.windows(2) {
+        let ((x, drawing), (next_x, _)) = (&pair[0], &pair[1]);
+        svg += &format!(
+            "<line x1=\"{:.1}\" y1=\"{cy:.1}\" x2=\"{:.1}\" y2=\"{cy:.1}\" stroke=\"#555\" \
+             stroke-width=\"{:.1}\"/>\n",
+            x,
+            next_x,
+            drawing.link_width,
+            cy = center_y
+        );
+    }
+    for (x, drawing) in &nodes {
+        svg += &drawing.body(*x, center_y);
+        svg += &format!(
+            "<text x=\"{:.1}\" y=\"{:.1}\" font-family=\"sans-serif\" font-size=\"10\" \
+             text-anchor=\"middle\" fill=\"#777\">{}</text>\n",
+            x,
+            height - MARGIN / 2.0,
+            drawing.node_id
+        );
+    }
+
+    svg + "</svg>\n"
+}
+
+/// Outline of a node around its center, `x` pointing to the front. Nodes without shape
+/// properties are circles.
+pub fn node_outline(node: &PhenotypeNode) -> Vec<(f64, f64)> {
+    NodeDrawing::new(0, node).outline
+}
+
+/// SVG fill color of a node, grey if it has no color.
+pub fn node_fill(node: &PhenotypeNode) -> String {
+    match finite_property(node, "hue") {
+        Some(hue) => format!(
+            "hsl({:.0}, {:.0}%, {:.0}%)",
+            hue,
+            property(node, "saturation", 0.5) * 100.0,
+            // Keeps the darkest and lightest colors visible on white
+            15.0 + property(node, "lightness", 0.5) * 70.0
+        ),
+        None => "hsl(0, 0%, 60%)".to_string(),
+    }
+}
+
+fn property(node: &PhenotypeNode, name: &str, default: f64) -> f64 {
+    finite_property(node, name).unwrap_or(default)
+}
+
+fn finite_property(node: &PhenotypeNode, name: &str) -> Option<f64> {
+    node.get_property(name).filter(|value| value.is_finite())
+}
+
+#[derive(Debug)]
+struct NodeDrawing {
+    node_id: u64,
+    fill: String,
+    radius: f64,
+    outline: Vec<(f64, f64)>,
+    speed: f64,
+    efficiency: Option<f64>,
+    sensor_range: f64,
+    spikes: usize,
+    curiosity: f64,
+    link_width: f64,
+}
+
+impl NodeDrawing {
+    fn new(node_id: u64, node: &PhenotypeNode) -> Self {
+        let radius = 8.0 + property(node, "size", 1.0).clamp(0.0, 10.0) * 3.0;
+        let segments = property(node, "segments", 1.0).clamp(1.0, 12.0).round();
+        let symmetry = property(node, "symmetry", 1.0).clamp(0.0, 1.0);
+        let stretch = property(node, "elongation", 1.0).clamp(0.25, 4.0).sqrt();
+        let lobe_depth = if segments > 1.0 { 0.2 } else { 0.0 };
+
+        let outline = (0..OUTLINE_POINTS)
+            .map(|index| {
+                let angle = TAU * index as f64 / OUTLINE_POINTS as f64;
+                let distance = radius
+                    * (1.0
+                        + lobe_depth * (segments * angle).cos()
+                        + 0.25 * (1.0 - symmetry) * angle.cos());
+                (
+                    distance * angle.cos() * stretch,
+                    distance * angle.sin() / stretch,
+                )
+            })
+            .collect();
+
+        Self {
+            node_id,
+            fill: node_fill(node),
+            radius,
+            outline,
+            speed: property(node, "speed", 0.0).clamp(0.0, 10.0),
+            efficiency: finite_property(node, "efficiency"),
+            sensor_range: property(node, "range", 0.0).max(0.0) * 2.0,
+            spikes: (property(node, "aggression", 0.0).clamp(0.0, 1.0) * 8.0).round() as usize,
+            curiosity: property(node, "curiosity", 0.0).clamp(0.0, 1.0),
+            link_width: 1.0 + property(node, "sociability", 0.0).clamp(0.0, 1.0) * 4.0,
+        }
+    }
+
+    /// Half width and half height around the center, including spikes and sensors.
+    fn get_extent(&self) -> (f64, f64) {
+        let (half_width, half_height) = self
+            .outline
+            .iter()
+            .fold((0.0, 0.0), |(width, height): (f64, f64), (x, y)| {
+                (width.max(x.abs()), height.max(y.abs()))
+            });
+        let spike = if self.spikes > 0 {
+            self.radius * 0.4
+        } else {
+            0.0
+        };
+        let sensor = self.radius + self.sensor_range;
+        let motion_lines = self.radius + 2.0 + self.speed * 3.0;
+        (
+            (half_width + spike).max(sensor).max(motion_lines),
+            (half_height + spike).max(sensor),
+        )
+    }
+
+    fn sensor(&self, x: f64, y: f64) -> String {
+        if self.sensor_range <= 0.0 {
+            return String::new();
+        }
+        format!(
+            "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"none\" stroke=\"#999\" \
+             stroke-dasharray=\"4 3\" opacity=\"0.6\"/>\n",
+            x,
+            y,
+            self.radius + self.sensor_range
+        )
+    }
+
+    fn body(&self, x: f64, y: f64) -> String {
+        let mut svg = String::new();
+
+        // Motion lines trailing behind the node
+        let motion_lines = (self.speed / 3.0).ceil() as usize;
+        for index in 0..motion_lines {
+            let offset = (index as f64 - (motion_lines as f64 - 1.0) / 2.0) * 5.0;
+            let start = x - self.radius;
+            svg += &format!(
+                "<line x1=\"{:.1}\" y1=\"{y:.1}\" x2=\"{:.1}\" y2=\"{y:.1}\" stroke=\"#888\" \
+                 stroke-linecap=\"round\"/>\n",
+                start - 2.0,
+                start - 2.0 - self.speed * 3.0,
+                y = y + offset
+            );
+        }
+
+        for index in 0..self.spikes {
+            let (px, py) = self.outline[index * OUTLINE_POINTS / self.spikes];
+            let length = 1.0 + 0.4 * self.radius / (px * px + py * py).sqrt().max(1.0);
+            svg += &format!(
+                "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"#333\" \
+                 stroke-width=\"2\"/>\n",
+                x + px,
+                y + py,
+                x + px * length,
+                y + py * length
+            );
+        }
+
+        let points: Vec<String> = self
+            .outline
+            .iter()
+            .map(|(px, py)| format!("{:.1},{:.1}", x + px, y + py))
+            .collect();
+        svg += &format!(
+            "<polygon points=\"{}\" fill=\"{}\" stroke=\"#333\" stroke-width=\"1.5\"/>\n",
+            points.join(" "),
+            self.fill
+        );
+
+        if let Some(efficiency) = self.efficiency {
+            svg += &format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"#000\" opacity=\"0.25\"/>\n",
+                x,
+                y,
+                self.radius * (0.15 + 0.25 * efficiency.clamp(0.0, 1.0))
+            );
+        }
+
+        if self.curiosity > 0.0 {
+            let front = self
+                .outline
+                .first()
+                .map(|(px, _)| *px)
+                .unwrap_or(self.radius);
+            let eye = 2.0 + self.curiosity * 4.0;
+            let eye_x = x + front - eye * 1.5;
+            svg += &format!(
+                "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"#fff\" stroke=\"#333\"/>\n\
+                 <circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"#000\"/>\n",
+                eye_x,
+                y - eye,
+                eye,
+                eye_x + eye * 0.3,
+                y - eye,
+                eye * 0.5
+            );
+        }
+
+        svg
+    }
+}
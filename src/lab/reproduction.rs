@@ -0,0 +1,45 @@
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use rand::{Rng, rng};
+
+/// Crosses two genomes with a single random crossover point per chromosome.
+///
+/// Chromosomes are paired by name, the offspring takes the head of the first and the tail of the
+/// second parent's chromosome. Chromosomes without partner are inherited from their parent, the
+/// ones of the first parent in its order, followed by the ones only the second parent has.
+pub fn cross(first: &Genome, second: &Genome) -> Genome {
+    let mut rng = rng();
+    let mut offspring = Genome::new();
+
+    for chromosome in first.get_chromosomes() {
+        let Some(partner) = second.get_chromosome(chromosome.get_name()) else {
+            push(&mut offspring, chromosome.clone());
+            continue;
+        };
+
+        let head = chromosome.get_sequence().get_sequence();
+        let tail = partner.get_sequence().get_sequence();
+        let point = rng.random_range(0..=head.len().min(tail.len()));
+
+        let mut nucleotides = head[..point].to_vec();
+        nucleotides.extend_from_slice(&tail[point..]);
+        push(
+            &mut offspring,
+            Chromosome::new(chromosome.get_name(), NucleotideSequence::new(nucleotides)),
+        );
+    }
+
+    for chromosome in second.get_chromosomes() {
+        if first.get_chromosome(chromosome.get_name()).is_none() {
+            push(&mut offspring, chromosome.clone());
+        }
+    }
+
+    offspring
+}
+
+fn push(genome: &mut Genome, chromosome: Chromosome) {
+    genome
+        .add_chromosome(chromosome)
+        .expect("Chromosome names of a genome are unique");
+}
@@ -0,0 +1,354 @@
+use crate::lab::Lab;
+use crate::lab::config::LabConfig;
+use crate::lab::error::{GenomeError, SessionError};
+use crate::lab::motif::{Motif, MotifMatch, NucleotidePattern, Strand};
+use crate::lab::mutation::{ChromosomeMutation, substitute_random};
+use crate::lab::patch::GenomePatch;
+use crate::lab::render::GenomeView;
+use crate::lab::render::phenotype_svg::phenotype_svg;
+use crate::lab::reproduction::cross;
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::gene::Gene;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::genome_format::GenomeFormat;
+use crate::lab::types::phenotype::Phenotype;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+pub const HELP: &str = "\
+config <path>                    reload the lab with a config file
+random <name> [count] [length]   create a random genome
+load <name> <path>               load a genome, the format is chosen by extension
+save <name> <path>               save a genome, the format is chosen by extension
+copy <name> <new name>           duplicate a genome
+drop <name>                      forget a genome
+list                             list genomes with their chromosomes
+show <name> [chromosome]         print the ATGC code
+bits <name> [chromosome]         print the packed bits
+codons <name> [chromosome]       print the codons
+protein <name> [chromosome]      print the amino acids
+render <name> [html path]        print the genome colored, or write it as HTML report
+find <name> <pattern>            find a nucleotide pattern on both strands, e.g. TATA[AT]A
+mutate <name> <rate>             substitute random nucleotides
+restructure <name>               apply a random fusion, fission or translocation
+cross <child> <first> <second>   cross two genomes
+diff <first> <second>            print the patch turning the first genome into the second
+patch <name> <path>              apply a patch file to a genome
+genes <name>                     list the genes read by the lab
+express <name>                   print the phenotype
+draw <name> <path>               draw the phenotype as SVG image
+help                             show this help";
+
+/// Keeps a lab and named genomes in memory and executes text commands on them, see [`HELP`].
+#[derive(Debug)]
+pub struct Session {
+    lab: Lab,
+    genomes: BTreeMap<String, Genome>,
+}
+
+impl Session {
+    pub fn new(lab: Lab) -> Self {
+        Self {
+            lab,
+            genomes: BTreeMap::new(),
+        }
+    }
+
+    pub fn get_lab(&self) -> &Lab {
+        &self.lab
+    }
+
+    pub fn get_genome(&self, name: &str) -> Option<&Genome> {
+        self.genomes.get(name)
+    }
+
+    pub fn set_genome(&mut self, name: &str, genome: Genome) {
+        self.genomes.insert(name.to_string(), genome);
+    }
+
+    /// Executes a single command line and returns its output. Empty lines produce no output.
+    pub fn execute(&mut self, line: &str) -> Result<String, SessionError> {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((command, arguments)) = words.split_first() else {
+            return Ok(String::new());
+        };
+
+        match (*command, arguments) {
+            ("help", []) => Ok(HELP.to_string()),
+            ("config", [path]) => {
+                let config = LabConfig::load(Path::new(path))?;
+                self.lab = Lab::with_builtin_genes(config);
+                Ok(format!("loaded config {}", path))
+            }
+            ("random", [name, rest @ ..]) if rest.len() <= 2 => {
+                let count = parse_argument(rest.first(), 1)?;
+                let length = parse_argument(rest.get(1), 300)?;
+                self.set_genome(name, Genome::random(count, length));
+                Ok(self.describe(name)?)
+            }
+            ("load", [name, path]) => {
+                let path = Path::new(path);
+                let genome = Genome::load_as(path, GenomeFormat::from_path(path))?;
+                self.set_genome(name, genome);
+                Ok(self.describe(name)?)
+            }
+            ("save", [name, path]) => {
+                let path = Path::new(path);
+                self.genome(name)?
+                    .save_as(path, GenomeFormat::from_path(path))?;
+                Ok(format!("saved {} to {}", name, path.display()))
+            }
+            ("copy", [name, new_name]) => {
+                let genome = self.genome(name)?.clone();
+                self.set_genome(new_name, genome);
+                Ok(self.describe(new_name)?)
+            }
+            ("drop", [name]) => {
+                self.genomes
+                    .remove(*name)
+                    .ok_or_else(|| SessionError::UnknownGenome(name.to_string()))?;
+                Ok(format!("dropped {}", name))
+            }
+            ("list", []) => Ok(self
+                .genomes
+                .keys()
+                .map(|name| self.describe(name))
+                .collect::<Result<Vec<_>, _>>()?
+                .join("\n")),
+            ("show", [name, rest @ ..]) if rest.len() <= 1 => {
+                self.per_chromosome(name, rest.first(), |chromosome| {
+                    chromosome.get_sequence().get_code_string()
+                })
+            }
+            ("bits", [name, rest @ ..]) if rest.len() <= 1 => {
+                self.per_chromosome(name, rest.first(), |chromosome| {
+                    chromosome.get_sequence().get_bit_string()
+                })
+            }
+            ("codons", [name, rest @ ..]) if rest.len() <= 1 => {
+                self.per_chromosome(name, rest.first(), |chromosome| {
+                    codon_view(&codons_of(chromosome))
+                })
+            }
+            ("protein", [name, rest @ ..]) if rest.len() <= 1 => {
+                self.per_chromosome(name, rest.first(), |chromosome| {
+                    protein_view(&codons_of(chromosome))
+                })
+            }
+            ("render", [name, rest @ ..]) if rest.len() <= 1 => {
+                let view = GenomeView::new(&self.lab, self.genome(name)?);
+                match rest.first() {
+                    Some(path) => {
+                        std::fs::write(path, view.to_html(name)).map_err(GenomeError::Io)?;
+                        Ok(format!("wrote {}", path))
+                    }
+                    None => Ok(view.to_ansi().trim_end().to_string()),
+                }
+            }
+            ("find", [name, pattern]) => {
+                let motif = Motif::Pattern(NucleotidePattern::parse(pattern)?);
+                let mut lines = Vec::new();
+                for chromosome in self.genome(name)?.get_chromosomes() {
+                    for found in
+                        motif.find_on_both_strands(chromosome.get_sequence().get_sequence())
+                    {
+                        lines.push(motif_match_view(chromosome.get_name(), &found));
+                    }
+                }
+                Ok(lines.join("\n"))
+            }
+            ("mutate", [name, rate]) => {
+                let rate: f64 = parse_argument(Some(rate), 0.0)?;
+                let genome = self.genome_mut(name)?;
+                let substitutions = substitute_random(genome, rate);
+                Ok(format!("{} substitutions", substitutions))
+            }
+            ("restructure", [name]) => {
+                let genome = self.genome_mut(name)?;
+                let Some(mutation) = ChromosomeMutation::random(genome) else {
+                    return Ok("no structural mutation possible".to_string());
+                };
+                mutation.apply(genome)?;
+                Ok(format!("{:?}", mutation))
+            }
+            ("cross", [child, first, second]) => {
+                let offspring = cross(self.genome(first)?, self.genome(second)?);
+                self.set_genome(child, offspring);
+                Ok(self.describe(child)?)
+            }
+            ("diff", [first, second]) => {
+                let original = self.genome(first)?;
+                let patch = GenomePatch::between(original, self.genome(second)?);
+                Ok(patch.to_annotated_text(original).trim_end().to_string())
+            }
+            ("patch", [name, path]) => {
+                let text = std::fs::read_to_string(path).map_err(GenomeError::Io)?;
+                let patched = GenomePatch::from_text(&text)?.apply(self.genome(name)?)?;
+                self.set_genome(name, patched);
+                Ok(self.describe(name)?)
+            }
+            ("genes", [name]) => Ok(self
+                .lab
+                .read_genome_genes(self.genome(name)?)
+                .iter()
+                .map(gene_view)
+                .collect::<Vec<_>>()
+                .join("\n")),
+            ("draw", [name, path]) => {
+                let phenotype = self.lab.express_genome(self.genome(name)?);
+                std::fs::write(path, phenotype_svg(&phenotype)).map_err(GenomeError::Io)?;
+                Ok(format!("wrote {}", path))
+            }
+            ("express", [name]) => Ok(phenotype_view(&self.lab.express_genome(self.genome(name)?))),
+            (command, _) if usage(command).is_some() => Err(SessionError::Usage(
+                usage(command).unwrap_or_default().to_string(),
+            )),
+            (command, _) => Err(SessionError::UnknownCommand(command.to_string())),
+        }
+    }
+
+    fn genome(&self, name: &str) -> Result<&Genome, SessionError> {
+        self.genomes
+            .get(name)
+            .ok_or_else(|| SessionError::UnknownGenome(name.to_string()))
+    }
+
+    fn genome_mut(&mut self, name: &str) -> Result<&mut Genome, SessionError> {
+        self.genomes
+            .get_mut(name)
+            .ok_or_else(|| SessionError::UnknownGenome(name.to_string()))
+    }
+
+    fn describe(&self, name: &str) -> Result<String, SessionError> {
+        let chromosomes: Vec<String> = self
+            .genome(name)?
+            .get_chromosomes()
+            .iter()
+            .map(|chromosome| {
+                format!(
+                    "{} ({})",
+                    chromosome.get_name(),
+                    chromosome.get_sequence().len()
+                )
+            })
+            .collect();
+        Ok(format!("{}: {}", name, chromosomes.join(", ")))
+    }
+
+    /// Applies the view to one or all chromosomes of a genome, prefixed by the chromosome name.
+    fn per_chromosome(
+        &self,
+        name: &str,
+        chromosome: Option<&&str>,
+        view: impl Fn(&Chromosome) -> String,
+    ) -> Result<String, SessionError> {
+        let genome = self.genome(name)?;
+        let chromosomes: Vec<&Chromosome> = match chromosome {
+            Some(chromosome) => vec![
+                genome
+                    .get_chromosome(chromosome)
+                    .ok_or_else(|| SessionError::UnknownChromosome(chromosome.to_string()))?,
+            ],
+            None => genome.get_chromosomes().iter().collect(),
+        };
+
+        Ok(chromosomes
+            .iter()
+            .map(|chromosome| format!("{}: {}", chromosome.get_name(), view(chromosome)))
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+}
+
+/// Line of the help describing the command.
+fn usage(command: &str) -> Option<&'static str> {
+    HELP.lines()
+        .find(|line| line.split_whitespace().next() == Some(command))
+}
+
+fn parse_argument<T: std::str::FromStr>(
+    value: Option<&&str>,
+    default: T,
+) -> Result<T, SessionError> {
+    match value {
+        Some(value) => value
+            .parse()
+            .map_err(|_| SessionError::InvalidArgument(value.to_string())),
+        None => Ok(default),
+    }
+}
+
+fn codons_of(chromosome: &Chromosome) -> CodonSequence {
+    CodonSequence::from_nucleotides(chromosome.get_sequence().get_sequence())
+}
+
+/// Codons separated by spaces, e.g. `ATG GCA`.
+pub fn codon_view(codons: &CodonSequence) -> String {
+    codons
+        .get_sequence()
+        .iter()
+        .map(|codon| {
+            codon
+                .to_nucleotides()
+                .iter()
+                .map(|nucleotide| char::from(*nucleotide))
+                .collect::<String>()
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// One letter amino acid codes, e.g. `MA*`.
+pub fn protein_view(codons: &CodonSequence) -> String {
+    codons
+        .get_sequence()
+        .iter()
+        .map(|codon| codon.get_type().get_symbol())
+        .collect()
+}
+
+/// Chromosome, range and strand of a match, e.g. `1: 12..18 -`.
+pub fn motif_match_view(chromosome: &str, found: &MotifMatch) -> String {
+    let strand = match found.strand {
+        Strand::Forward => '+',
+        Strand::Reverse => '-',
+    };
+    match found.score {
+        Some(score) => format!(
+            "{}: {}..{} {} {:.3}",
+            chromosome, found.start, found.end, strand, score
+        ),
+        None => format!("{}: {}..{} {}", chromosome, found.start, found.end, strand),
+    }
+}
+
+pub fn gene_view(gene: &Gene) -> String {
+    let properties: Vec<String> = gene
+        .get_properties()
+        .iter()
+        .map(|(name, value)| format!("{}={:.3}", name, value))
+        .collect();
+    format!(
+        "{:<14} node {:<5} strength {:.3} {}",
+        gene.get_gene_type().name(),
+        gene.get_node_id(),
+        gene.get_strength(),
+        properties.join(" ")
+    )
+}
+
+pub fn phenotype_view(phenotype: &Phenotype) -> String {
+    phenotype
+        .get_nodes()
+        .iter()
+        .map(|(node_id, node)| {
+            let properties: Vec<String> = node
+                .get_properties()
+                .iter()
+                .map(|(name, value)| format!("{}={:.3}", name, value))
+                .collect();
+            format!("node {}: {}", node_id, properties.join(" "))
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,94 @@
+use crate::lab::config::LabConfig;
+use crate::lab::error::SnapshotError;
+use crate::lab::types::gene_type::{GeneType, GeneTypeTrait};
+use crate::lab::types::organism::Organism;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Version of the snapshot layout, snapshots of other versions are rejected.
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+/// Tag of the built-in [`GeneType`]s, their data is the gene type name.
+pub const BUILTIN_GENE_TYPE_TAG: &str = "builtin";
+
+/// Restores a gene type from the data written by [`GeneTypeTrait::snapshot`].
+pub type GeneTypeConstructor = fn(&serde_yaml::Value) -> Result<Box<dyn GeneTypeTrait>, String>;
+
+/// A gene type implementation, identified by a tag which is looked up in a [`GeneTypeRegistry`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GeneTypeSnapshot {
+    pub tag: String,
+    #[serde(default)]
+    pub data: serde_yaml::Value,
+}
+
+impl GeneTypeSnapshot {
+    pub fn new(tag: &str, data: serde_yaml::Value) -> Self {
+        Self {
+            tag: tag.to_string(),
+            data,
+        }
+    }
+}
+
+/// Complete state of a lab, see [`crate::lab::Lab::snapshot`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LabSnapshot {
+    pub version: u32,
+    pub config: LabConfig,
+    pub gene_types: Vec<GeneTypeSnapshot>,
+    #[serde(default)]
+    pub populations: BTreeMap<String, Vec<Organism>>,
+}
+
+/// Maps snapshot tags to the constructors of gene type implementations.
+#[derive(Debug, Clone, Default)]
+pub struct GeneTypeRegistry {
+    constructors: BTreeMap<String, GeneTypeConstructor>,
+}
+
+impl GeneTypeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registry which knows the built-in gene types.
+    pub fn with_builtin() -> Self {
+        let mut registry = Self::new();
+        registry.register(BUILTIN_GENE_TYPE_TAG, restore_builtin);
+        registry
+    }
+
+    /// Registers a constructor, replacing an earlier one with the same tag.
+    pub fn register(&mut self, tag: &str, constructor: GeneTypeConstructor) {
+        self.constructors.insert(tag.to_string(), constructor);
+    }
+
+    pub fn contains(&self, tag: &str) -> bool {
+        self.constructors.contains_key(tag)
+    }
+
+    pub fn restore(
+        &self,
+        snapshot: &GeneTypeSnapshot,
+    ) -> Result<Box<dyn GeneTypeTrait>, SnapshotError> {
+        let constructor = self
+            .constructors
+            .get(&snapshot.tag)
+            .ok_or_else(|| SnapshotError::UnknownGeneTypeTag(snapshot.tag.clone()))?;
+
+        constructor(&snapshot.data).map_err(|message| SnapshotError::InvalidGeneType {
+            tag: snapshot.tag.clone(),
+            message,
+        })
+    }
+}
+
+fn restore_builtin(data: &serde_yaml::Value) -> Result<Box<dyn GeneTypeTrait>, String> {
+    let name = data
+        .as_str()
+        .ok_or_else(|| "expected the name of a built-in gene type".to_string())?;
+    GeneType::from_name(name)
+        .map(|gene_type| Box::new(gene_type) as Box<dyn GeneTypeTrait>)
+        .ok_or_else(|| format!("'{}' is not a built-in gene type", name))
+}
@@ -1,6 +1,18 @@
 pub mod codon;
 pub mod codon_sequence;
+pub mod dominance;
 pub mod gene;
+pub mod gene_payload;
 pub mod gene_type;
+pub mod genome;
+pub mod genome_format;
 pub mod nucleotide;
 pub mod nucleotide_sequence;
+pub mod organism;
+pub mod phenotype;
+pub mod regulation;
+pub mod ribonucleotide;
+pub mod rna_sequence;
+pub mod sequence_serde;
+pub mod splicing;
+pub mod transcription;
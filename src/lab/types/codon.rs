@@ -1,6 +1,8 @@
 use crate::lab::types::nucleotide::{Nucleotide, Nucleotide::*};
 use rand::rng;
 use rand::seq::IndexedRandom;
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 const IDENTIFYING_CODON_TYPES: [CodonType; 19] = [
     CodonType::Ala,
@@ -50,7 +52,8 @@ pub enum Codon {
     Val(CodonVAL),
 }
 
-#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum CodonType {
     Met,
     Stop,
@@ -155,6 +158,18 @@ impl Codon {
         Some(codon)
     }
 
+    pub fn from_code_string(code: &str) -> Option<Self> {
+        let nucleotides = code
+            .chars()
+            .map(Nucleotide::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .ok()?;
+        if nucleotides.len() != 3 {
+            return None;
+        }
+        Self::from_nucleotides(&nucleotides)
+    }
+
     pub fn to_nucleotides(&self) -> [Nucleotide; 3] {
         match self {
             Self::Phe(CodonPHE::TTT) => [T, T, T],
@@ -224,6 +239,49 @@ impl Codon {
         }
     }
 
+    pub fn get_index(&self) -> u8 {
+        let [n1, n2, n3] = self.to_nucleotides();
+        ((n1 as u8) << 4) | ((n2 as u8) << 2) | n3 as u8
+    }
+
+    /// Inverse of [`Self::get_index`].
+    pub fn from_index(index: u8) -> Option<Self> {
+        if index > 0b11_11_11 {
+            return None;
+        }
+        Self::from_nucleotides(&[
+            Nucleotide::from(index >> 4),
+            Nucleotide::from(index >> 2),
+            Nucleotide::from(index),
+        ])
+    }
+
+    pub fn get_type(&self) -> CodonType {
+        match self {
+            Self::Met => CodonType::Met,
+            Self::Stop(_) => CodonType::Stop,
+            Self::Ala(_) => CodonType::Ala,
+            Self::Arg(_) => CodonType::Arg,
+            Self::Asn(_) => CodonType::Asn,
+            Self::Asp(_) => CodonType::Asp,
+            Self::Cys(_) => CodonType::Cys,
+            Self::Gln(_) => CodonType::Gln,
+            Self::Glu(_) => CodonType::Glu,
+            Self::Gly(_) => CodonType::Gly,
+            Self::His(_) => CodonType::His,
+            Self::Ile(_) => CodonType::Ile,
+            Self::Leu(_) => CodonType::Leu,
+            Self::Lys(_) => CodonType::Lys,
+            Self::Phe(_) => CodonType::Phe,
+            Self::Pro(_) => CodonType::Pro,
+            Self::Ser(_) => CodonType::Ser,
+            Self::Thr(_) => CodonType::Thr,
+            Self::Trp => CodonType::Trp,
+            Self::Tyr(_) => CodonType::Tyr,
+            Self::Val(_) => CodonType::Val,
+        }
+    }
+
     pub fn random_from_type(codon_type: CodonType) -> Self {
         match codon_type {
             CodonType::Met => Self::Met,
@@ -251,13 +309,75 @@ impl Codon {
     }
 }
 
+/// Written as its nucleotides (e.g. `"ATG"`) to human-readable formats and as its index otherwise.
+impl Serialize for Codon {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            let code: String = self.to_nucleotides().map(char::from).iter().collect();
+            serializer.serialize_str(&code)
+        } else {
+            serializer.serialize_u8(self.get_index())
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Codon {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let code = String::deserialize(deserializer)?;
+            Codon::from_code_string(&code)
+                .ok_or_else(|| D::Error::custom(format!("'{}' is not a codon", code)))
+        } else {
+            let index = u8::deserialize(deserializer)?;
+            Codon::from_index(index)
+                .ok_or_else(|| D::Error::custom(format!("{} is not a codon index", index)))
+        }
+    }
+}
+
 impl CodonType {
+    /// Inverse of [`Self::get_symbol`], case-insensitive.
+    pub fn from_symbol(symbol: char) -> Option<Self> {
+        let symbol = symbol.to_ascii_uppercase();
+        [CodonType::Met, CodonType::Stop]
+            .into_iter()
+            .chain(IDENTIFYING_CODON_TYPES)
+            .find(|codon_type| codon_type.get_symbol() == symbol)
+    }
+
+    /// One letter amino acid code, `*` for stop codons.
+    pub fn get_symbol(&self) -> char {
+        match self {
+            CodonType::Met => 'M',
+            CodonType::Stop => '*',
+            CodonType::Ala => 'A',
+            CodonType::Arg => 'R',
+            CodonType::Asn => 'N',
+            CodonType::Asp => 'D',
+            CodonType::Cys => 'C',
+            CodonType::Gln => 'Q',
+            CodonType::Glu => 'E',
+            CodonType::Gly => 'G',
+            CodonType::His => 'H',
+            CodonType::Ile => 'I',
+            CodonType::Leu => 'L',
+            CodonType::Lys => 'K',
+            CodonType::Phe => 'F',
+            CodonType::Pro => 'P',
+            CodonType::Ser => 'S',
+            CodonType::Thr => 'T',
+            CodonType::Trp => 'W',
+            CodonType::Tyr => 'Y',
+            CodonType::Val => 'V',
+        }
+    }
+
     pub fn gene_type_id(value: u32, length: u8) -> Vec<Self> {
         let mut result = Vec::with_capacity(length as usize);
         let mut remaining = value;
 
         for _ in (0..length).rev() {
-            let index = (remaining as usize % IDENTIFYING_CODON_TYPES.len());
+            let index = remaining as usize % IDENTIFYING_CODON_TYPES.len();
             result.push(IDENTIFYING_CODON_TYPES[index]);
             remaining /= IDENTIFYING_CODON_TYPES.len() as u32;
         }
@@ -1,6 +1,9 @@
+use crate::lab::config::protein_config::ProteinConfig;
+use crate::lab::protein::ProteinProperties;
 use crate::lab::types::codon::Codon;
 use crate::lab::types::nucleotide::Nucleotide;
 use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::splicing::SpliceSites;
 
 #[derive(Debug, Clone, PartialEq)]
 pub struct CodonSequence {
@@ -20,6 +23,11 @@ impl CodonSequence {
         &self.sequence
     }
 
+    /// Trailing nucleotides which don't form a complete codon.
+    pub fn get_remainder(&self) -> &[Nucleotide] {
+        &self.remainder
+    }
+
     pub fn random(length: u32) -> Self {
         let nucleotide_sequence = NucleotideSequence::random(length as u64 * 3);
         Self::from_nucleotides(nucleotide_sequence.get_sequence())
@@ -32,6 +40,18 @@ impl CodonSequence {
         Self::new(codons, remainder.to_vec())
     }
 
+    pub fn from_spliced_nucleotides(
+        nucleotides: &[Nucleotide],
+        splice_sites: &SpliceSites,
+    ) -> Self {
+        let exons = splice_sites.splice(&NucleotideSequence::new(nucleotides.to_vec()));
+        Self::from_nucleotides(exons.get_sequence())
+    }
+
+    pub fn protein_properties(&self, config: &ProteinConfig) -> ProteinProperties {
+        ProteinProperties::from_codons(&self.sequence, config)
+    }
+
     pub fn to_nucleotides(&self) -> Vec<Nucleotide> {
         let mut nucleotides = Vec::with_capacity(self.sequence.len() * 3 + self.remainder.len());
 
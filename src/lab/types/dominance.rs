@@ -0,0 +1,87 @@
+use crate::lab::types::gene::{Gene, GeneProperties, blend_hue};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// How alleles of the same gene type on the same node combine in polyploid organisms.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Dominance {
+    /// The strongest allele masks all others.
+    Dominant,
+    /// Only expressed if every homolog carries the gene, the strongest allele is expressed then.
+    Recessive,
+    /// All alleles are expressed side by side.
+    CoDominant,
+    /// All alleles blend into one gene, properties are averaged weighted by strength. Hue is
+    /// averaged around the color wheel.
+    Incomplete,
+}
+
+impl Dominance {
+    /// Resolves the alleles of one gene type on one node.
+    ///
+    /// `homolog_count` is the amount of distinct homologs the alleles were read from.
+    pub fn resolve(self, alleles: Vec<Gene>, homolog_count: usize, ploidy: usize) -> Vec<Gene> {
+        if alleles.len() < 2 && self != Dominance::Recessive {
+            return alleles;
+        }
+
+        match self {
+            Dominance::Dominant => strongest(alleles).into_iter().collect(),
+            Dominance::Recessive if homolog_count >= ploidy => {
+                strongest(alleles).into_iter().collect()
+            }
+            Dominance::Recessive => vec![],
+            Dominance::CoDominant => alleles,
+            Dominance::Incomplete => vec![blend(&alleles)],
+        }
+    }
+}
+
+fn strongest(alleles: Vec<Gene>) -> Option<Gene> {
+    alleles
+        .into_iter()
+        .max_by(|a, b| a.get_strength().total_cmp(&b.get_strength()))
+}
+
+fn blend(alleles: &[Gene]) -> Gene {
+    let total_strength: f64 = alleles.iter().map(|gene| gene.get_strength() as f64).sum();
+    let weight = |gene: &Gene| {
+        if total_strength > 0.0 {
+            gene.get_strength() as f64 / total_strength
+        } else {
+            1.0 / alleles.len() as f64
+        }
+    };
+
+    let mut properties: BTreeMap<String, (f64, f64)> = BTreeMap::new();
+    for gene in alleles {
+        for (name, value) in gene.get_properties() {
+            let (weighted_sum, total_weight) = properties.entry(name.clone()).or_default();
+            *weighted_sum += value * weight(gene);
+            *total_weight += weight(gene);
+        }
+    }
+    let mut properties: GeneProperties = properties
+        .into_iter()
+        .map(|(name, (weighted_sum, total_weight))| (name, weighted_sum / total_weight))
+        .collect();
+    if let Some(hue) = blend_hue(alleles.iter().map(|gene| (gene, weight(gene)))) {
+        properties.insert("hue".to_string(), hue);
+    }
+
+    let first = &alleles[0];
+    let strength = (total_strength / alleles.len() as f64) as f32;
+    let gene = Gene::new(
+        first.get_gene_type().clone_boxed(),
+        first.get_node_id(),
+        strength,
+        properties,
+    );
+
+    match first.get_regulation() {
+        Some(regulation) => gene.with_regulation(regulation.clone()),
+        None => gene,
+    }
+}
@@ -1,23 +1,129 @@
+use crate::lab::config::LabConfig;
 use crate::lab::types::codon::Codon;
 use crate::lab::types::gene_type::GeneTypeTrait;
+use crate::lab::types::regulation::Regulation;
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+pub type GeneProperties = BTreeMap<String, f64>;
 
 #[derive(Debug)]
 pub struct Gene {
     gene_type: Box<dyn GeneTypeTrait>,
     node_id: u64,
     strength: f32,
+    properties: GeneProperties,
+    regulation: Option<Regulation>,
 }
 
 impl Gene {
-    pub fn new(gene_type: Box<dyn GeneTypeTrait>, node_id: u64, strength: f32) -> Self {
+    pub fn new(
+        gene_type: Box<dyn GeneTypeTrait>,
+        node_id: u64,
+        strength: f32,
+        properties: GeneProperties,
+    ) -> Self {
         Self {
             gene_type,
             node_id,
             strength,
+            properties,
+            regulation: None,
+        }
+    }
+
+    pub fn with_regulation(mut self, regulation: Regulation) -> Self {
+        self.regulation = Some(regulation);
+        self
+    }
+
+    pub fn get_gene_type(&self) -> &dyn GeneTypeTrait {
+        self.gene_type.as_ref()
+    }
+
+    pub fn get_node_id(&self) -> u64 {
+        self.node_id
+    }
+
+    pub fn get_strength(&self) -> f32 {
+        self.strength
+    }
+
+    pub fn set_strength(&mut self, strength: f32) {
+        self.strength = strength;
+    }
+
+    pub fn get_properties(&self) -> &GeneProperties {
+        &self.properties
+    }
+
+    pub fn get_property(&self, name: &str) -> Option<f64> {
+        self.properties.get(name).copied()
+    }
+
+    pub fn get_regulation(&self) -> Option<&Regulation> {
+        self.regulation.as_ref()
+    }
+
+    pub fn from_gene_type(
+        gene_type: Box<dyn GeneTypeTrait>,
+        remaining_sequence: &[Codon],
+        config: &LabConfig,
+    ) -> Self {
+        gene_type.parse_codon_sequence(remaining_sequence, config)
+    }
+}
+
+impl Clone for Gene {
+    fn clone(&self) -> Self {
+        Self {
+            gene_type: self.gene_type.clone_boxed(),
+            node_id: self.node_id,
+            strength: self.strength,
+            properties: self.properties.clone(),
+            regulation: self.regulation.clone(),
         }
     }
+}
+
+/// A gene together with the codons it was read from, from the `Met` codon to the stop codon.
+#[derive(Debug, Clone)]
+pub struct GeneLocation {
+    gene: Gene,
+    codons: Range<usize>,
+}
+
+impl GeneLocation {
+    pub fn new(gene: Gene, codons: Range<usize>) -> Self {
+        Self { gene, codons }
+    }
 
-    pub fn from_gene_type(gene_type: Box<dyn GeneTypeTrait>, remaining_sequence: &[Codon]) -> Self {
-        gene_type.parse_codon_sequence(remaining_sequence)
+    pub fn get_gene(&self) -> &Gene {
+        &self.gene
     }
+
+    pub fn get_codons(&self) -> Range<usize> {
+        self.codons.clone()
+    }
+
+    pub fn into_gene(self) -> Gene {
+        self.gene
+    }
+}
+
+/// Blends the `hue` of the weighted genes as a circular mean, so hues on both sides of 0° meet
+/// there instead of in the middle of the color wheel. `None` if no gene has a hue.
+pub fn blend_hue<'a>(genes: impl IntoIterator<Item = (&'a Gene, f64)>) -> Option<f64> {
+    let (mut sin, mut cos, mut found) = (0.0, 0.0, false);
+    for (gene, weight) in genes {
+        if let Some(hue) = gene.get_property("hue") {
+            sin += hue.to_radians().sin() * weight;
+            cos += hue.to_radians().cos() * weight;
+            found = true;
+        }
+    }
+
+    // Rounding can push tiny negative angles up to exactly 360
+    let hue = sin.atan2(cos).to_degrees().rem_euclid(360.0);
+    found.then_some(if hue >= 360.0 { 0.0 } else { hue })
 }
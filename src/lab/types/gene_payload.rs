@@ -0,0 +1,84 @@
+use crate::lab::config::codon_value_config::CodonValueConfig;
+use crate::lab::config::protein_config::ProteinConfig;
+use crate::lab::protein::ProteinProperties;
+use crate::lab::types::codon::Codon;
+
+const CODON_VALUES: u64 = 64;
+
+/// Reads values from the codons following a gene's id sequence.
+///
+/// Every codon encodes a digit from 0 to 63. Reading past the end of the payload yields zeros, so
+/// truncated genes still parse into (weak) values instead of failing.
+#[derive(Debug, Clone)]
+pub struct GenePayload<'a> {
+    codons: &'a [Codon],
+    position: usize,
+}
+
+impl<'a> GenePayload<'a> {
+    pub fn new(codons: &'a [Codon]) -> Self {
+        Self {
+            codons,
+            position: 0,
+        }
+    }
+
+    pub fn get_codons(&self) -> &'a [Codon] {
+        self.codons
+    }
+
+    pub fn remaining(&self) -> &'a [Codon] {
+        &self.codons[self.position.min(self.codons.len())..]
+    }
+
+    /// Mean of the configured values over all codons of the payload, 0.0 for an empty payload.
+    pub fn mean_value(&self, config: &CodonValueConfig<f64>) -> f64 {
+        if self.codons.is_empty() {
+            return 0.0;
+        }
+
+        let total: f64 = self
+            .codons
+            .iter()
+            .map(|codon| config.get_value(*codon))
+            .sum();
+        total / self.codons.len() as f64
+    }
+
+    /// Physical properties of the whole payload read as a protein.
+    pub fn protein_properties(&self, config: &ProteinConfig) -> ProteinProperties {
+        ProteinProperties::from_codons(self.codons, config)
+    }
+
+    pub fn next_codon(&mut self) -> Option<Codon> {
+        let codon = self.codons.get(self.position).copied();
+        self.position += 1;
+        codon
+    }
+
+    pub fn next_digit(&mut self) -> u8 {
+        let digit = self
+            .codons
+            .get(self.position)
+            .map(|codon| codon.get_index())
+            .unwrap_or(0);
+        self.position += 1;
+        digit
+    }
+
+    /// Reads a big-endian base-64 number spanning the given amount of codons.
+    pub fn next_value(&mut self, codon_count: u32) -> u64 {
+        (0..codon_count).fold(0, |value, _| {
+            value * CODON_VALUES + self.next_digit() as u64
+        })
+    }
+
+    /// Reads a single codon as a value between 0.0 and 1.0.
+    pub fn next_unit(&mut self) -> f64 {
+        self.next_digit() as f64 / (CODON_VALUES - 1) as f64
+    }
+
+    pub fn next_range(&mut self, min: f64, max: f64) -> f64 {
+        min + self.next_unit() * (max - min)
+    }
+}
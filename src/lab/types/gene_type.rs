@@ -1,37 +1,216 @@
+use crate::lab::config::LabConfig;
+use crate::lab::snapshot::{BUILTIN_GENE_TYPE_TAG, GeneTypeSnapshot};
 use crate::lab::types::codon::{Codon, CodonType};
-use crate::lab::types::gene::Gene;
+use crate::lab::types::dominance::Dominance;
+use crate::lab::types::gene::{Gene, GeneProperties};
+use crate::lab::types::gene_payload::GenePayload;
+use crate::lab::types::regulation::{Regulation, RegulationKind, RegulationTarget};
 use std::fmt::Debug;
-use std::hash::{DefaultHasher, Hash, Hasher};
+
+/// Amount of codons at the start of every gene payload which encode the node the gene belongs to.
+pub const NODE_ID_CODONS: u32 = 2;
 
 pub trait GeneTypeTrait: Debug {
+    fn name(&self) -> &str;
     fn id_sequence(&self) -> Vec<CodonType>;
-    fn parse_codon_sequence(&self, sequence: &[Codon]) -> Gene;
+    /// Parses the codons between the id sequence and the stop codon of a gene.
+    fn parse_codon_sequence(&self, sequence: &[Codon], config: &LabConfig) -> Gene;
     fn clone_boxed(&self) -> Box<dyn GeneTypeTrait>;
 
-    fn id_sequence_hash(&self) -> u64 {
-        let mut hasher = DefaultHasher::new();
-        self.id_sequence().hash(&mut hasher);
-        hasher.finish()
+    fn dominance(&self) -> Dominance {
+        Dominance::CoDominant
+    }
+
+    /// Tagged data to restore the gene type from a lab snapshot, see
+    /// [`crate::lab::snapshot::GeneTypeRegistry`]. Gene types without snapshot can't be saved.
+    fn snapshot(&self) -> Option<GeneTypeSnapshot> {
+        None
     }
 }
 
-#[derive(Debug, Clone)]
+/// Built-in gene types.
+///
+/// Every payload starts with the node id ([`NODE_ID_CODONS`] codons), followed by one codon per
+/// property in the documented order. The strength of a gene is the mean `codon_strength` of its
+/// payload.
+///
+/// Regulatory genes have no properties. Their payload continues with a target mode codon (even:
+/// gene type, odd: node), the target (one codon type as id prefix, or a node id) and a `magnitude`
+/// codon (0-1).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum GeneType {
+    /// `hue` (0-360), `saturation` (0-1), `lightness` (0-1)
     Color,
+    /// `size` (0.1-10)
+    Size,
+    /// `segments` (1-12), `symmetry` (0-1, bilateral to radial), `elongation` (0.25-4)
+    Shape,
+    /// `speed` (0-10)
+    Speed,
+    /// `efficiency` (0-1), `rate` (0.1-5), plus `hydrophobicity` and `charge` of the whole payload
+    /// read as a protein
+    Metabolism,
+    /// `range` (0-50)
+    SensorRange,
+    /// `aggression` (0-1), `sociability` (0-1), `curiosity` (0-1)
+    Behaviour,
+    Promoter,
+    Enhancer,
+    Repressor,
+}
+
+impl GeneType {
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|gene_type| gene_type.name() == name)
+    }
+
+    pub fn all() -> Vec<Self> {
+        vec![
+            Self::Color,
+            Self::Size,
+            Self::Shape,
+            Self::Speed,
+            Self::Metabolism,
+            Self::SensorRange,
+            Self::Behaviour,
+            Self::Promoter,
+            Self::Enhancer,
+            Self::Repressor,
+        ]
+    }
+
+    pub fn regulation_kind(&self) -> Option<RegulationKind> {
+        match self {
+            GeneType::Promoter => Some(RegulationKind::Promoter),
+            GeneType::Enhancer => Some(RegulationKind::Enhancer),
+            GeneType::Repressor => Some(RegulationKind::Repressor),
+            _ => None,
+        }
+    }
+
+    fn parse_regulation(&self, payload: &mut GenePayload) -> Option<Regulation> {
+        let kind = self.regulation_kind()?;
+        let target = if payload.next_digit().is_multiple_of(2) {
+            let id_prefix = payload
+                .next_codon()
+                .map(|codon| vec![codon.get_type()])
+                .unwrap_or_default();
+            RegulationTarget::GeneType(id_prefix)
+        } else {
+            RegulationTarget::Node(payload.next_value(NODE_ID_CODONS))
+        };
+
+        Some(Regulation {
+            kind,
+            target,
+            magnitude: payload.next_unit() as f32,
+        })
+    }
+
+    fn parse_properties(&self, payload: &mut GenePayload, config: &LabConfig) -> GeneProperties {
+        let mut properties = GeneProperties::new();
+        let mut set = |name: &str, value: f64| {
+            properties.insert(name.to_string(), value);
+        };
+
+        match self {
+            GeneType::Color => {
+                set("hue", payload.next_range(0.0, 360.0));
+                set("saturation", payload.next_unit());
+                set("lightness", payload.next_unit());
+            }
+            GeneType::Size => set("size", payload.next_range(0.1, 10.0)),
+            GeneType::Shape => {
+                set("segments", (payload.next_digit() % 12 + 1) as f64);
+                set("symmetry", payload.next_unit());
+                set("elongation", payload.next_range(0.25, 4.0));
+            }
+            GeneType::Speed => set("speed", payload.next_range(0.0, 10.0)),
+            GeneType::Metabolism => {
+                set("efficiency", payload.next_unit());
+                set("rate", payload.next_range(0.1, 5.0));
+                let protein = payload.protein_properties(&config.protein);
+                set("hydrophobicity", protein.hydrophobicity);
+                set("charge", protein.charge);
+            }
+            GeneType::SensorRange => set("range", payload.next_range(0.0, 50.0)),
+            GeneType::Behaviour => {
+                set("aggression", payload.next_unit());
+                set("sociability", payload.next_unit());
+                set("curiosity", payload.next_unit());
+            }
+            GeneType::Promoter | GeneType::Enhancer | GeneType::Repressor => {}
+        }
+
+        properties
+    }
 }
 
 impl GeneTypeTrait for GeneType {
+    fn name(&self) -> &str {
+        match self {
+            GeneType::Color => "color",
+            GeneType::Size => "size",
+            GeneType::Shape => "shape",
+            GeneType::Speed => "speed",
+            GeneType::Metabolism => "metabolism",
+            GeneType::SensorRange => "sensor_range",
+            GeneType::Behaviour => "behaviour",
+            GeneType::Promoter => "promoter",
+            GeneType::Enhancer => "enhancer",
+            GeneType::Repressor => "repressor",
+        }
+    }
+
     fn id_sequence(&self) -> Vec<CodonType> {
         match self {
             GeneType::Color => CodonType::gene_type_id(0, 1),
+            GeneType::Size => CodonType::gene_type_id(1, 1),
+            GeneType::Shape => CodonType::gene_type_id(2, 1),
+            GeneType::Speed => CodonType::gene_type_id(3, 1),
+            GeneType::Metabolism => CodonType::gene_type_id(4, 1),
+            GeneType::SensorRange => CodonType::gene_type_id(5, 1),
+            GeneType::Behaviour => CodonType::gene_type_id(6, 1),
+            GeneType::Promoter => CodonType::gene_type_id(7, 1),
+            GeneType::Enhancer => CodonType::gene_type_id(8, 1),
+            GeneType::Repressor => CodonType::gene_type_id(9, 1),
         }
     }
 
-    fn parse_codon_sequence(&self, sequence: &[Codon]) -> Gene {
-        todo!()
+    fn parse_codon_sequence(&self, sequence: &[Codon], config: &LabConfig) -> Gene {
+        let mut payload = GenePayload::new(sequence);
+        let strength = payload.mean_value(&config.codon_strength);
+        let node_id = payload.next_value(NODE_ID_CODONS);
+        let properties = self.parse_properties(&mut payload, config);
+        let gene = Gene::new(self.clone_boxed(), node_id, strength as f32, properties);
+
+        match self.parse_regulation(&mut payload) {
+            Some(regulation) => gene.with_regulation(regulation),
+            None => gene,
+        }
     }
 
     fn clone_boxed(&self) -> Box<dyn GeneTypeTrait> {
-        Box::new(self.clone())
+        Box::new(*self)
+    }
+
+    fn dominance(&self) -> Dominance {
+        match self {
+            GeneType::Color | GeneType::Size | GeneType::Speed | GeneType::SensorRange => {
+                Dominance::Incomplete
+            }
+            GeneType::Shape | GeneType::Metabolism => Dominance::Dominant,
+            GeneType::Behaviour => Dominance::Recessive,
+            GeneType::Promoter | GeneType::Enhancer | GeneType::Repressor => Dominance::CoDominant,
+        }
+    }
+
+    fn snapshot(&self) -> Option<GeneTypeSnapshot> {
+        Some(GeneTypeSnapshot::new(
+            BUILTIN_GENE_TYPE_TAG,
+            serde_yaml::Value::String(self.name().to_string()),
+        ))
     }
 }
@@ -0,0 +1,203 @@
+use crate::lab::error::GenomeError;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+const GENOME_MAGIC: &[u8; 4] = b"BLGN";
+/// Longest chromosome name in bytes, names are stored with a u16 length.
+pub const MAX_NAME_LENGTH: usize = u16::MAX as usize;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Chromosome {
+    name: String,
+    sequence: NucleotideSequence,
+}
+
+impl Chromosome {
+    pub fn new(name: impl Into<String>, sequence: NucleotideSequence) -> Self {
+        Self {
+            name: name.into(),
+            sequence,
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn get_sequence(&self) -> &NucleotideSequence {
+        &self.sequence
+    }
+
+    pub fn set_sequence(&mut self, sequence: NucleotideSequence) {
+        self.sequence = sequence;
+    }
+}
+
+/// A set of uniquely named chromosomes, kept in insertion order.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(try_from = "GenomeData")]
+pub struct Genome {
+    chromosomes: Vec<Chromosome>,
+}
+
+/// Deserialized genome before the chromosome names are checked.
+#[derive(Deserialize)]
+struct GenomeData {
+    chromosomes: Vec<Chromosome>,
+}
+
+impl TryFrom<GenomeData> for Genome {
+    type Error = GenomeError;
+
+    fn try_from(data: GenomeData) -> Result<Self, Self::Error> {
+        let mut genome = Genome::new();
+        for chromosome in data.chromosomes {
+            genome.add_chromosome(chromosome)?;
+        }
+        Ok(genome)
+    }
+}
+
+impl Genome {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn random(chromosome_count: usize, length: u64) -> Self {
+        let chromosomes = (1..=chromosome_count)
+            .map(|i| Chromosome::new(i.to_string(), NucleotideSequence::random(length)))
+            .collect();
+        Self { chromosomes }
+    }
+
+    pub fn get_chromosomes(&self) -> &[Chromosome] {
+        &self.chromosomes
+    }
+
+    pub fn get_chromosome(&self, name: &str) -> Option<&Chromosome> {
+        self.chromosomes
+            .iter()
+            .find(|chromosome| chromosome.name == name)
+    }
+
+    pub fn get_chromosome_mut(&mut self, name: &str) -> Option<&mut Chromosome> {
+        self.chromosomes
+            .iter_mut()
+            .find(|chromosome| chromosome.name == name)
+    }
+
+    pub fn index_of(&self, name: &str) -> Option<usize> {
+        self.chromosomes
+            .iter()
+            .position(|chromosome| chromosome.name == name)
+    }
+
+    pub fn add_chromosome(&mut self, chromosome: Chromosome) -> Result<(), GenomeError> {
+        self.insert_chromosome(self.chromosomes.len(), chromosome)
+    }
+
+    pub fn insert_chromosome(
+        &mut self,
+        index: usize,
+        chromosome: Chromosome,
+    ) -> Result<(), GenomeError> {
+        if chromosome.name.len() > MAX_NAME_LENGTH {
+            return Err(GenomeError::NameTooLong(chromosome.name.len()));
+        }
+        if self.get_chromosome(&chromosome.name).is_some() {
+            return Err(GenomeError::DuplicateChromosome(chromosome.name));
+        }
+        self.chromosomes
+            .insert(index.min(self.chromosomes.len()), chromosome);
+        Ok(())
+    }
+
+    pub fn remove_chromosome(&mut self, name: &str) -> Result<Chromosome, GenomeError> {
+        let index = self
+            .index_of(name)
+            .ok_or_else(|| GenomeError::UnknownChromosome(name.to_string()))?;
+        Ok(self.chromosomes.remove(index))
+    }
+
+    pub fn total_length(&self) -> usize {
+        self.chromosomes
+            .iter()
+            .map(|chromosome| chromosome.sequence.len())
+            .sum()
+    }
+
+    /// Layout: magic, chromosome count (u32), then per chromosome the name length (u16), the UTF-8
+    /// name, the packed sequence length (u32) and the packed sequence. All integers little endian.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = GENOME_MAGIC.to_vec();
+        bytes.extend_from_slice(&(self.chromosomes.len() as u32).to_le_bytes());
+
+        for chromosome in &self.chromosomes {
+            let name = chromosome.name.as_bytes();
+            bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(name);
+
+            let sequence = chromosome.sequence.to_bytes();
+            bytes.extend_from_slice(&(sequence.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&sequence);
+        }
+
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, GenomeError> {
+        let mut reader = ByteReader { bytes, position: 0 };
+        if reader.take(GENOME_MAGIC.len())? != GENOME_MAGIC {
+            return Err(GenomeError::InvalidBytes(
+                "missing genome header".to_string(),
+            ));
+        }
+
+        let chromosome_count = u32::from_le_bytes(reader.take_array()?);
+        let mut genome = Self::new();
+        for _ in 0..chromosome_count {
+            let name_length = u16::from_le_bytes(reader.take_array()?) as usize;
+            let name = String::from_utf8(reader.take(name_length)?.to_vec()).map_err(|_| {
+                GenomeError::InvalidBytes("chromosome name is not valid UTF-8".to_string())
+            })?;
+
+            let sequence_length = u32::from_le_bytes(reader.take_array()?) as usize;
+            let sequence_bytes = reader.take(sequence_length)?;
+            let sequence = NucleotideSequence::try_from_bytes(sequence_bytes)?;
+            genome.add_chromosome(Chromosome::new(name, sequence))?;
+        }
+
+        Ok(genome)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), GenomeError> {
+        std::fs::write(path, self.to_bytes())?;
+        Ok(())
+    }
+
+    pub fn load(path: &Path) -> Result<Self, GenomeError> {
+        Self::from_bytes(&std::fs::read(path)?)
+    }
+}
+
+struct ByteReader<'a> {
+    bytes: &'a [u8],
+    position: usize,
+}
+
+impl<'a> ByteReader<'a> {
+    fn take(&mut self, length: usize) -> Result<&'a [u8], GenomeError> {
+        let end = self.position + length;
+        let slice = self
+            .bytes
+            .get(self.position..end)
+            .ok_or_else(|| GenomeError::InvalidBytes("unexpected end of data".to_string()))?;
+        self.position = end;
+        Ok(slice)
+    }
+
+    fn take_array<const N: usize>(&mut self) -> Result<[u8; N], GenomeError> {
+        Ok(self.take(N)?.try_into().unwrap())
+    }
+}
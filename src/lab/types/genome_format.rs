@@ -0,0 +1,163 @@
+use crate::lab::error::GenomeError;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use std::path::Path;
+
+/// Nucleotides per line in FASTA files.
+pub const FASTA_LINE_WIDTH: usize = 60;
+
+/// File formats of genomes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GenomeFormat {
+    /// Packed bytes, see [`Genome::to_bytes`].
+    Bytes,
+    /// One record per chromosome, the chromosome name is the first word of the header.
+    Fasta,
+    /// One line of `ATGC` per chromosome, chromosomes are named by their line number on reading.
+    Text,
+}
+
+impl GenomeFormat {
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "bin" | "blgn" => Some(GenomeFormat::Bytes),
+            "fa" | "fasta" | "fna" => Some(GenomeFormat::Fasta),
+            "txt" | "atgc" => Some(GenomeFormat::Text),
+            _ => None,
+        }
+    }
+
+    /// Files without a known extension are treated as packed bytes.
+    pub fn from_path(path: &Path) -> Self {
+        path.extension()
+            .and_then(|extension| extension.to_str())
+            .and_then(Self::from_extension)
+            .unwrap_or(GenomeFormat::Bytes)
+    }
+
+    pub fn encode(&self, genome: &Genome) -> Vec<u8> {
+        match self {
+            GenomeFormat::Bytes => genome.to_bytes(),
+            GenomeFormat::Fasta => genome.to_fasta().into_bytes(),
+            GenomeFormat::Text => genome.to_text().into_bytes(),
+        }
+    }
+
+    pub fn decode(&self, bytes: &[u8]) -> Result<Genome, GenomeError> {
+        match self {
+            GenomeFormat::Bytes => Genome::from_bytes(bytes),
+            GenomeFormat::Fasta | GenomeFormat::Text => {
+                let text = std::str::from_utf8(bytes).map_err(|_| GenomeError::InvalidText {
+                    line: 0,
+                    reason: "not valid UTF-8".to_string(),
+                })?;
+                match self {
+                    GenomeFormat::Fasta => Genome::from_fasta(text),
+                    _ => Genome::from_text(text),
+                }
+            }
+        }
+    }
+}
+
+impl Genome {
+    pub fn to_fasta(&self) -> String {
+        let mut fasta = String::new();
+        for chromosome in self.get_chromosomes() {
+            fasta.push('>');
+            fasta.push_str(chromosome.get_name());
+            fasta.push('\n');
+            for line in chromosome
+                .get_sequence()
+                .get_sequence()
+                .chunks(FASTA_LINE_WIDTH)
+            {
+                fasta.extend(line.iter().map(|nucleotide| char::from(*nucleotide)));
+                fasta.push('\n');
+            }
+        }
+        fasta
+    }
+
+    /// Sequence lines may be wrapped at any width, empty lines and `;` comments are skipped.
+    pub fn from_fasta(fasta: &str) -> Result<Self, GenomeError> {
+        let mut genome = Genome::new();
+        let mut current: Option<(String, Vec<Nucleotide>)> = None;
+
+        for (index, line) in fasta.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with(';') {
+                continue;
+            }
+
+            if let Some(header) = line.strip_prefix('>') {
+                if let Some((name, nucleotides)) = current.take() {
+                    genome.add_chromosome(Chromosome::new(
+                        name,
+                        NucleotideSequence::new(nucleotides),
+                    ))?;
+                }
+                let name = header.split_whitespace().next().unwrap_or_default();
+                current = Some((name.to_string(), Vec::new()));
+                continue;
+            }
+
+            let Some((_, nucleotides)) = current.as_mut() else {
+                return Err(GenomeError::InvalidText {
+                    line: index + 1,
+                    reason: "sequence before the first header".to_string(),
+                });
+            };
+            nucleotides.extend(parse_line(line, index + 1)?);
+        }
+
+        if let Some((name, nucleotides)) = current {
+            genome.add_chromosome(Chromosome::new(name, NucleotideSequence::new(nucleotides)))?;
+        }
+        Ok(genome)
+    }
+
+    pub fn to_text(&self) -> String {
+        self.get_chromosomes()
+            .iter()
+            .map(|chromosome| chromosome.get_sequence().get_code_string() + "\n")
+            .collect()
+    }
+
+    /// Empty lines are skipped, the remaining chromosomes are named `1`, `2`, ...
+    pub fn from_text(text: &str) -> Result<Self, GenomeError> {
+        let mut genome = Genome::new();
+        for (index, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let name = (genome.get_chromosomes().len() + 1).to_string();
+            let sequence = NucleotideSequence::new(parse_line(line, index + 1)?);
+            genome.add_chromosome(Chromosome::new(name, sequence))?;
+        }
+        Ok(genome)
+    }
+
+    pub fn save_as(&self, path: &Path, format: GenomeFormat) -> Result<(), GenomeError> {
+        std::fs::write(path, format.encode(self))?;
+        Ok(())
+    }
+
+    pub fn load_as(path: &Path, format: GenomeFormat) -> Result<Self, GenomeError> {
+        format.decode(&std::fs::read(path)?)
+    }
+}
+
+fn parse_line(line: &str, line_number: usize) -> Result<Vec<Nucleotide>, GenomeError> {
+    line.chars()
+        .filter(|character| !character.is_whitespace())
+        .map(|character| {
+            Nucleotide::try_from(character).map_err(|invalid| GenomeError::InvalidText {
+                line: line_number,
+                reason: format!("'{}' is not a nucleotide", invalid),
+            })
+        })
+        .collect()
+}
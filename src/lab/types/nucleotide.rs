@@ -1,3 +1,6 @@
+use serde::de::Error;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Nucleotide {
     A = 0b00,
@@ -39,3 +42,49 @@ impl From<Nucleotide> for char {
         }
     }
 }
+
+impl From<Nucleotide> for u8 {
+    fn from(nucleotide: Nucleotide) -> u8 {
+        char::from(nucleotide) as u8
+    }
+}
+
+impl TryFrom<char> for Nucleotide {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value.to_ascii_uppercase() {
+            'A' => Ok(Nucleotide::A),
+            'T' => Ok(Nucleotide::T),
+            'G' => Ok(Nucleotide::G),
+            'C' => Ok(Nucleotide::C),
+            _ => Err(value),
+        }
+    }
+}
+
+/// Written as a character to human-readable formats and as its two bits otherwise.
+impl Serialize for Nucleotide {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_char(char::from(*self))
+        } else {
+            serializer.serialize_u8(*self as u8)
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Nucleotide {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            let value = char::deserialize(deserializer)?;
+            Nucleotide::try_from(value)
+                .map_err(|invalid| D::Error::custom(format!("'{}' is not a nucleotide", invalid)))
+        } else {
+            match u8::deserialize(deserializer)? {
+                value @ 0..=3 => Ok(Nucleotide::from(value)),
+                value => Err(D::Error::custom(format!("{} is not a nucleotide", value))),
+            }
+        }
+    }
+}
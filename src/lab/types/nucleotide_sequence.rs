@@ -1,4 +1,7 @@
+use crate::lab::error::GenomeError;
 use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::rna_sequence::RnaSequence;
+use crate::lab::types::transcription::TranscriptionMotifs;
 use rand::random_range;
 
 const BYTE_SEQUENCE_HEADER_SIZE: usize = 1;
@@ -17,6 +20,14 @@ impl NucleotideSequence {
         &self.sequence
     }
 
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
     pub fn random(length: u64) -> Self {
         let mut sequence = Vec::with_capacity(length as usize);
         for _ in 0..length {
@@ -29,7 +40,7 @@ impl NucleotideSequence {
     pub fn to_bytes(&self) -> Vec<u8> {
         // Will determine if the last byte contains an incomplete sequence of less than 4 bytes
         let mask_byte = (self.sequence.len() % 4) as u8;
-        let byte_count = (self.sequence.len() + 3) / 4;
+        let byte_count = self.sequence.len().div_ceil(4);
 
         let mut bytes = vec![0u8; byte_count + 1];
         bytes[0] = mask_byte;
@@ -43,12 +54,14 @@ impl NucleotideSequence {
         bytes
     }
 
+    /// Reads bytes written by [`Self::to_bytes`], a damaged header byte is only used modulo 4.
+    /// Use [`Self::try_from_bytes`] for bytes from untrusted sources.
     pub fn from_bytes(bytes: &[u8]) -> Self {
         if bytes.is_empty() {
             return Self::new(vec![]);
         }
 
-        let mask_byte = bytes[0] as usize;
+        let mask_byte = (bytes[0] % 4) as usize;
         let mut sequence = Vec::new();
 
         let last_byte_index = bytes.len().saturating_sub(1 + BYTE_SEQUENCE_HEADER_SIZE);
@@ -74,6 +87,25 @@ impl NucleotideSequence {
         Self::new(sequence)
     }
 
+    /// Like [`Self::from_bytes`], but rejects a header byte which can't have been written by
+    /// [`Self::to_bytes`].
+    pub fn try_from_bytes(bytes: &[u8]) -> Result<Self, GenomeError> {
+        if let Some(mask_byte) = bytes.first() {
+            if *mask_byte > 3 {
+                return Err(GenomeError::InvalidBytes(format!(
+                    "invalid header byte {} of packed nucleotides",
+                    mask_byte
+                )));
+            }
+            if *mask_byte != 0 && bytes.len() == BYTE_SEQUENCE_HEADER_SIZE {
+                return Err(GenomeError::InvalidBytes(
+                    "packed nucleotides are missing".to_string(),
+                ));
+            }
+        }
+        Ok(Self::from_bytes(bytes))
+    }
+
     pub fn get_bit_string(&self) -> String {
         self.to_bytes()
             .iter()
@@ -83,6 +115,19 @@ impl NucleotideSequence {
             .join("")
     }
 
+    pub fn transcribe(&self, motifs: &TranscriptionMotifs) -> Vec<RnaSequence> {
+        motifs.transcribe(self)
+    }
+
+    /// Parses a string of `A`, `T`, `G` and `C` (case-insensitive), failing on the first other
+    /// character.
+    pub fn from_code_string(code: &str) -> Result<Self, char> {
+        code.chars()
+            .map(Nucleotide::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+
     pub fn get_code_string(&self) -> String {
         self.sequence
             .iter()
@@ -90,3 +135,16 @@ impl NucleotideSequence {
             .collect::<String>()
     }
 }
+
+/// Position of the first occurrence of the motif at or after `from`.
+pub(crate) fn find_motif(
+    nucleotides: &[Nucleotide],
+    motif: &[Nucleotide],
+    from: usize,
+) -> Option<usize> {
+    nucleotides
+        .get(from..)?
+        .windows(motif.len())
+        .position(|window| window == motif)
+        .map(|position| position + from)
+}
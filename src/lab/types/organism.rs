@@ -0,0 +1,38 @@
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use serde::{Deserialize, Serialize};
+
+/// An organism carrying one or more homologous copies of its genome.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Organism {
+    homologs: Vec<NucleotideSequence>,
+}
+
+impl Organism {
+    pub fn new(homologs: Vec<NucleotideSequence>) -> Self {
+        Self { homologs }
+    }
+
+    pub fn haploid(sequence: NucleotideSequence) -> Self {
+        Self::new(vec![sequence])
+    }
+
+    pub fn diploid(first: NucleotideSequence, second: NucleotideSequence) -> Self {
+        Self::new(vec![first, second])
+    }
+
+    pub fn random(ploidy: usize, length: u64) -> Self {
+        Self::new(
+            (0..ploidy)
+                .map(|_| NucleotideSequence::random(length))
+                .collect(),
+        )
+    }
+
+    pub fn get_homologs(&self) -> &[NucleotideSequence] {
+        &self.homologs
+    }
+
+    pub fn get_ploidy(&self) -> usize {
+        self.homologs.len()
+    }
+}
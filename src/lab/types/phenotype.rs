@@ -0,0 +1,76 @@
+use crate::lab::types::gene::{Gene, GeneProperties, blend_hue};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhenotypeNode {
+    properties: GeneProperties,
+}
+
+impl PhenotypeNode {
+    pub fn get_properties(&self) -> &GeneProperties {
+        &self.properties
+    }
+
+    pub fn get_property(&self, name: &str) -> Option<f64> {
+        self.properties.get(name).copied()
+    }
+}
+
+/// The expressed entity, made up of nodes which collect the properties of the genes targeting them.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Phenotype {
+    nodes: BTreeMap<u64, PhenotypeNode>,
+}
+
+impl Phenotype {
+    /// Properties set by several genes on the same node are averaged, weighted by gene strength,
+    /// hue around the color wheel. Genes without any strength or properties are not expressed at
+    /// all.
+    pub fn from_genes(genes: &[Gene]) -> Self {
+        let mut totals: BTreeMap<u64, BTreeMap<&str, (f64, f64)>> = BTreeMap::new();
+        let mut expressed: BTreeMap<u64, Vec<&Gene>> = BTreeMap::new();
+
+        for gene in genes
+            .iter()
+            .filter(|gene| gene.get_strength() > 0.0 && !gene.get_properties().is_empty())
+        {
+            let strength = gene.get_strength() as f64;
+            expressed.entry(gene.get_node_id()).or_default().push(gene);
+            let node = totals.entry(gene.get_node_id()).or_default();
+            for (name, value) in gene.get_properties() {
+                let (weighted_sum, total_strength) = node.entry(name).or_default();
+                *weighted_sum += value * strength;
+                *total_strength += strength;
+            }
+        }
+
+        let nodes = totals
+            .into_iter()
+            .map(|(node_id, properties)| {
+                let mut properties: GeneProperties = properties
+                    .into_iter()
+                    .map(|(name, (weighted_sum, total_strength))| {
+                        (name.to_string(), weighted_sum / total_strength)
+                    })
+                    .collect();
+                let genes = expressed[&node_id]
+                    .iter()
+                    .map(|gene| (*gene, gene.get_strength() as f64));
+                if let Some(hue) = blend_hue(genes) {
+                    properties.insert("hue".to_string(), hue);
+                }
+                (node_id, PhenotypeNode { properties })
+            })
+            .collect();
+
+        Self { nodes }
+    }
+
+    pub fn get_nodes(&self) -> &BTreeMap<u64, PhenotypeNode> {
+        &self.nodes
+    }
+
+    pub fn get_node(&self, node_id: u64) -> Option<&PhenotypeNode> {
+        self.nodes.get(&node_id)
+    }
+}
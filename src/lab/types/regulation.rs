@@ -0,0 +1,38 @@
+use crate::lab::types::codon::CodonType;
+use crate::lab::types::gene::Gene;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegulationKind {
+    /// Adds the regulator's expression to the target's strength.
+    Promoter,
+    /// Scales the target's strength up by the regulator's expression.
+    Enhancer,
+    /// Scales the target's strength down by the regulator's expression.
+    Repressor,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegulationTarget {
+    /// Every gene whose id sequence starts with the given codon types.
+    GeneType(Vec<CodonType>),
+    /// Every gene expressed on the given node.
+    Node(u64),
+}
+
+impl RegulationTarget {
+    pub fn matches(&self, gene: &Gene) -> bool {
+        match self {
+            RegulationTarget::GeneType(id_prefix) => {
+                gene.get_gene_type().id_sequence().starts_with(id_prefix)
+            }
+            RegulationTarget::Node(node_id) => gene.get_node_id() == *node_id,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regulation {
+    pub kind: RegulationKind,
+    pub target: RegulationTarget,
+    pub magnitude: f32,
+}
@@ -0,0 +1,57 @@
+use crate::lab::types::nucleotide::Nucleotide;
+
+/// RNA base, uracil takes the place of thymine.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Ribonucleotide {
+    A = 0b00,
+    U = 0b01,
+    G = 0b10,
+    C = 0b11,
+}
+
+impl From<Nucleotide> for Ribonucleotide {
+    fn from(nucleotide: Nucleotide) -> Self {
+        match nucleotide {
+            Nucleotide::A => Ribonucleotide::A,
+            Nucleotide::T => Ribonucleotide::U,
+            Nucleotide::G => Ribonucleotide::G,
+            Nucleotide::C => Ribonucleotide::C,
+        }
+    }
+}
+
+impl From<Ribonucleotide> for Nucleotide {
+    fn from(ribonucleotide: Ribonucleotide) -> Self {
+        match ribonucleotide {
+            Ribonucleotide::A => Nucleotide::A,
+            Ribonucleotide::U => Nucleotide::T,
+            Ribonucleotide::G => Nucleotide::G,
+            Ribonucleotide::C => Nucleotide::C,
+        }
+    }
+}
+
+impl From<Ribonucleotide> for char {
+    fn from(ribonucleotide: Ribonucleotide) -> char {
+        match ribonucleotide {
+            Ribonucleotide::A => 'A',
+            Ribonucleotide::U => 'U',
+            Ribonucleotide::G => 'G',
+            Ribonucleotide::C => 'C',
+        }
+    }
+}
+
+impl TryFrom<char> for Ribonucleotide {
+    type Error = char;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value.to_ascii_uppercase() {
+            'A' => Ok(Ribonucleotide::A),
+            'U' => Ok(Ribonucleotide::U),
+            'G' => Ok(Ribonucleotide::G),
+            'C' => Ok(Ribonucleotide::C),
+            _ => Err(value),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::ribonucleotide::Ribonucleotide;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RnaSequence {
+    sequence: Vec<Ribonucleotide>,
+}
+
+impl RnaSequence {
+    pub fn new(sequence: Vec<Ribonucleotide>) -> Self {
+        Self { sequence }
+    }
+
+    pub fn get_sequence(&self) -> &[Ribonucleotide] {
+        &self.sequence
+    }
+
+    pub fn len(&self) -> usize {
+        self.sequence.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sequence.is_empty()
+    }
+
+    pub fn from_nucleotides(nucleotides: &[Nucleotide]) -> Self {
+        Self::new(
+            nucleotides
+                .iter()
+                .map(|nucleotide| (*nucleotide).into())
+                .collect(),
+        )
+    }
+
+    pub fn to_nucleotides(&self) -> Vec<Nucleotide> {
+        self.sequence
+            .iter()
+            .map(|ribonucleotide| (*ribonucleotide).into())
+            .collect()
+    }
+
+    pub fn translate(&self) -> CodonSequence {
+        CodonSequence::from_nucleotides(&self.to_nucleotides())
+    }
+
+    pub fn from_code_string(code: &str) -> Result<Self, char> {
+        code.chars()
+            .map(Ribonucleotide::try_from)
+            .collect::<Result<Vec<_>, _>>()
+            .map(Self::new)
+    }
+
+    pub fn get_code_string(&self) -> String {
+        self.sequence
+            .iter()
+            .map(|ribonucleotide| char::from(*ribonucleotide))
+            .collect::<String>()
+    }
+}
+
+impl From<&NucleotideSequence> for RnaSequence {
+    fn from(sequence: &NucleotideSequence) -> Self {
+        Self::from_nucleotides(sequence.get_sequence())
+    }
+}
@@ -0,0 +1,207 @@
+//! Serde representations of nucleotide based sequences.
+//!
+//! By default sequences are written as `ATGC` strings to human-readable formats and as packed
+//! bytes (see [`NucleotideSequence::to_bytes`]) to binary formats. The modules [`code`],
+//! [`base64`] and [`readable`] select a representation explicitly, e.g.
+//! `#[serde(with = "sequence_serde::base64")]`. Deserializing accepts every representation.
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use ::base64::Engine;
+use ::base64::engine::general_purpose::STANDARD;
+use serde::de::{Error, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt::Formatter;
+
+/// Marks base64 strings, which would otherwise be ambiguous with `ATGC` strings.
+pub const BASE64_PREFIX: &str = "base64:";
+
+/// Nucleotides per group in the readable representation.
+const READABLE_GROUP_SIZE: usize = 3;
+
+/// Sequences which are stored as a plain list of nucleotides.
+pub trait NucleotideSerde: Sized {
+    fn to_nucleotide_vec(&self) -> Vec<Nucleotide>;
+    fn from_nucleotide_vec(nucleotides: Vec<Nucleotide>) -> Self;
+}
+
+impl NucleotideSerde for NucleotideSequence {
+    fn to_nucleotide_vec(&self) -> Vec<Nucleotide> {
+        self.get_sequence().to_vec()
+    }
+
+    fn from_nucleotide_vec(nucleotides: Vec<Nucleotide>) -> Self {
+        NucleotideSequence::new(nucleotides)
+    }
+}
+
+impl NucleotideSerde for CodonSequence {
+    fn to_nucleotide_vec(&self) -> Vec<Nucleotide> {
+        self.to_nucleotides()
+    }
+
+    fn from_nucleotide_vec(nucleotides: Vec<Nucleotide>) -> Self {
+        CodonSequence::from_nucleotides(&nucleotides)
+    }
+}
+
+/// `ATGC` string, e.g. `"ATGGCA"`.
+pub mod code {
+    use super::*;
+
+    pub fn serialize<T: NucleotideSerde, S: Serializer>(
+        sequence: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let nucleotides = NucleotideSequence::new(sequence.to_nucleotide_vec());
+        serializer.serialize_str(&nucleotides.get_code_string())
+    }
+
+    pub fn deserialize<'de, T: NucleotideSerde, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
+
+/// Packed bytes as base64 with [`BASE64_PREFIX`], e.g. `"base64:ABrF"`.
+pub mod base64 {
+    use super::*;
+
+    pub fn serialize<T: NucleotideSerde, S: Serializer>(
+        sequence: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let bytes = NucleotideSequence::new(sequence.to_nucleotide_vec()).to_bytes();
+        serializer.serialize_str(&format!("{}{}", BASE64_PREFIX, STANDARD.encode(bytes)))
+    }
+
+    pub fn deserialize<'de, T: NucleotideSerde, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
+
+/// `ATGC` string split into codons, e.g. `"ATG GCA"`.
+pub mod readable {
+    use super::*;
+
+    pub fn serialize<T: NucleotideSerde, S: Serializer>(
+        sequence: &T,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let groups: Vec<String> = sequence
+            .to_nucleotide_vec()
+            .chunks(READABLE_GROUP_SIZE)
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|nucleotide| char::from(*nucleotide))
+                    .collect()
+            })
+            .collect();
+        serializer.serialize_str(&groups.join(" "))
+    }
+
+    pub fn deserialize<'de, T: NucleotideSerde, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<T, D::Error> {
+        super::deserialize(deserializer)
+    }
+}
+
+pub(crate) fn serialize<T: NucleotideSerde, S: Serializer>(
+    sequence: &T,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    if serializer.is_human_readable() {
+        code::serialize(sequence, serializer)
+    } else {
+        let bytes = NucleotideSequence::new(sequence.to_nucleotide_vec()).to_bytes();
+        serializer.serialize_bytes(&bytes)
+    }
+}
+
+pub(crate) fn deserialize<'de, T: NucleotideSerde, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<T, D::Error> {
+    let nucleotides = if deserializer.is_human_readable() {
+        deserializer.deserialize_any(SequenceVisitor)?
+    } else {
+        deserializer.deserialize_bytes(SequenceVisitor)?
+    };
+    Ok(T::from_nucleotide_vec(nucleotides))
+}
+
+fn parse_str<E: Error>(value: &str) -> Result<Vec<Nucleotide>, E> {
+    if let Some(encoded) = value.strip_prefix(BASE64_PREFIX) {
+        let bytes = STANDARD.decode(encoded.trim()).map_err(E::custom)?;
+        return parse_bytes(&bytes);
+    }
+
+    value
+        .chars()
+        .filter(|character| !character.is_whitespace())
+        .map(|character| {
+            Nucleotide::try_from(character)
+                .map_err(|invalid| E::custom(format!("'{}' is not a nucleotide", invalid)))
+        })
+        .collect()
+}
+
+fn parse_bytes<E: Error>(bytes: &[u8]) -> Result<Vec<Nucleotide>, E> {
+    NucleotideSequence::try_from_bytes(bytes)
+        .map(|sequence| sequence.get_sequence().to_vec())
+        .map_err(E::custom)
+}
+
+struct SequenceVisitor;
+
+impl<'de> Visitor<'de> for SequenceVisitor {
+    type Value = Vec<Nucleotide>;
+
+    fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+        formatter.write_str("an ATGC string, a base64 string, packed bytes or a nucleotide list")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<Self::Value, E> {
+        parse_str(value)
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<Self::Value, E> {
+        parse_bytes(value)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut nucleotides = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(nucleotide) = seq.next_element::<Nucleotide>()? {
+            nucleotides.push(nucleotide);
+        }
+        Ok(nucleotides)
+    }
+}
+
+impl Serialize for NucleotideSequence {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for NucleotideSequence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer)
+    }
+}
+
+impl Serialize for CodonSequence {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serialize(self, serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for CodonSequence {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserialize(deserializer)
+    }
+}
@@ -0,0 +1,127 @@
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::{NucleotideSequence, find_motif};
+use std::ops::Range;
+
+pub const DEFAULT_DONOR_MOTIF: &str = "GTAAGT";
+pub const DEFAULT_ACCEPTOR_MOTIF: &str = "TTTCAG";
+
+/// Donor and acceptor motifs which mark the start and the end of an intron.
+///
+/// Introns include both motifs and are removed entirely when splicing.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpliceSites {
+    donor: Vec<Nucleotide>,
+    acceptor: Vec<Nucleotide>,
+}
+
+impl Default for SpliceSites {
+    fn default() -> Self {
+        Self::from_code_strings(DEFAULT_DONOR_MOTIF, DEFAULT_ACCEPTOR_MOTIF).unwrap()
+    }
+}
+
+impl SpliceSites {
+    pub fn new(donor: Vec<Nucleotide>, acceptor: Vec<Nucleotide>) -> Self {
+        Self { donor, acceptor }
+    }
+
+    pub fn from_code_strings(donor: &str, acceptor: &str) -> Result<Self, char> {
+        Ok(Self::new(
+            NucleotideSequence::from_code_string(donor)?
+                .get_sequence()
+                .to_vec(),
+            NucleotideSequence::from_code_string(acceptor)?
+                .get_sequence()
+                .to_vec(),
+        ))
+    }
+
+    pub fn get_donor(&self) -> &[Nucleotide] {
+        &self.donor
+    }
+
+    pub fn get_acceptor(&self) -> &[Nucleotide] {
+        &self.acceptor
+    }
+
+    /// Finds introns from left to right, pairing every donor with the closest acceptor after it.
+    pub fn find_introns(&self, nucleotides: &[Nucleotide]) -> Vec<Range<usize>> {
+        let mut introns = Vec::new();
+        if self.donor.is_empty() || self.acceptor.is_empty() {
+            return introns;
+        }
+
+        let mut position = 0;
+        while let Some(start) = find_motif(nucleotides, &self.donor, position) {
+            let Some(acceptor_start) =
+                find_motif(nucleotides, &self.acceptor, start + self.donor.len())
+            else {
+                break;
+            };
+
+            let end = acceptor_start + self.acceptor.len();
+            introns.push(start..end);
+            position = end;
+        }
+
+        introns
+    }
+
+    pub fn find_exons(&self, nucleotides: &[Nucleotide]) -> Vec<Range<usize>> {
+        let mut exons = Vec::new();
+        let mut start = 0;
+        for intron in self.find_introns(nucleotides) {
+            exons.push(start..intron.start);
+            start = intron.end;
+        }
+        exons.push(start..nucleotides.len());
+        exons
+    }
+
+    /// Removes all introns.
+    pub fn splice(&self, sequence: &NucleotideSequence) -> NucleotideSequence {
+        let nucleotides = sequence.get_sequence();
+        let exons = self.find_exons(nucleotides);
+        join_exons(nucleotides, exons.iter())
+    }
+
+    /// Splices the sequence in different ways by skipping internal exons, the first and last exon
+    /// are always kept. The fully spliced transcript comes first, at most `max_transcripts` are
+    /// returned. Only the first 63 internal exons are ever skipped.
+    pub fn alternative_transcripts(
+        &self,
+        sequence: &NucleotideSequence,
+        max_transcripts: usize,
+    ) -> Vec<NucleotideSequence> {
+        let nucleotides = sequence.get_sequence();
+        let exons = self.find_exons(nucleotides);
+        let skippable = exons.len().saturating_sub(2).min(63);
+
+        let mut transcripts = Vec::new();
+        // Every bit of the mask skips one internal exon, mask 0 keeps all of them
+        let mut skip_mask: u64 = 0;
+        while transcripts.len() < max_transcripts && skip_mask < 1 << skippable {
+            let kept = exons.iter().enumerate().filter(|(i, _)| {
+                *i == 0
+                    || *i == exons.len() - 1
+                    || *i > skippable
+                    || skip_mask & (1 << (i - 1)) == 0
+            });
+            transcripts.push(join_exons(nucleotides, kept.map(|(_, exon)| exon)));
+            skip_mask += 1;
+        }
+
+        transcripts
+    }
+}
+
+fn join_exons<'a>(
+    nucleotides: &[Nucleotide],
+    exons: impl Iterator<Item = &'a Range<usize>>,
+) -> NucleotideSequence {
+    NucleotideSequence::new(
+        exons
+            .flat_map(|exon| nucleotides[exon.clone()].iter().copied())
+            .collect(),
+    )
+}
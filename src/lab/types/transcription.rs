@@ -0,0 +1,89 @@
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::{NucleotideSequence, find_motif};
+use crate::lab::types::rna_sequence::RnaSequence;
+use std::ops::Range;
+
+pub const DEFAULT_PROMOTER_MOTIF: &str = "TATAAT";
+pub const DEFAULT_TERMINATOR_MOTIF: &str = "TTTTTT";
+
+/// Motifs which start and end transcription.
+///
+/// Transcription begins right after a promoter and stops right before the next terminator, or at
+/// the end of the sequence if there is none. Neither motif is part of the transcript.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TranscriptionMotifs {
+    promoter: Vec<Nucleotide>,
+    terminator: Vec<Nucleotide>,
+}
+
+impl Default for TranscriptionMotifs {
+    fn default() -> Self {
+        Self::from_code_strings(DEFAULT_PROMOTER_MOTIF, DEFAULT_TERMINATOR_MOTIF).unwrap()
+    }
+}
+
+impl TranscriptionMotifs {
+    pub fn new(promoter: Vec<Nucleotide>, terminator: Vec<Nucleotide>) -> Self {
+        Self {
+            promoter,
+            terminator,
+        }
+    }
+
+    pub fn from_code_strings(promoter: &str, terminator: &str) -> Result<Self, char> {
+        Ok(Self::new(
+            NucleotideSequence::from_code_string(promoter)?
+                .get_sequence()
+                .to_vec(),
+            NucleotideSequence::from_code_string(terminator)?
+                .get_sequence()
+                .to_vec(),
+        ))
+    }
+
+    pub fn get_promoter(&self) -> &[Nucleotide] {
+        &self.promoter
+    }
+
+    pub fn get_terminator(&self) -> &[Nucleotide] {
+        &self.terminator
+    }
+
+    pub fn find_transcribed_regions(&self, nucleotides: &[Nucleotide]) -> Vec<Range<usize>> {
+        let mut regions = Vec::new();
+        if self.promoter.is_empty() {
+            return regions;
+        }
+
+        let mut position = 0;
+        while let Some(promoter_start) = find_motif(nucleotides, &self.promoter, position) {
+            let start = promoter_start + self.promoter.len();
+            let end = if self.terminator.is_empty() {
+                None
+            } else {
+                find_motif(nucleotides, &self.terminator, start)
+            };
+
+            match end {
+                Some(end) => {
+                    regions.push(start..end);
+                    position = end + self.terminator.len();
+                }
+                None => {
+                    regions.push(start..nucleotides.len());
+                    break;
+                }
+            }
+        }
+
+        regions
+    }
+
+    pub fn transcribe(&self, sequence: &NucleotideSequence) -> Vec<RnaSequence> {
+        let nucleotides = sequence.get_sequence();
+        self.find_transcribed_regions(nucleotides)
+            .into_iter()
+            .map(|region| RnaSequence::from_nucleotides(&nucleotides[region]))
+            .collect()
+    }
+}
@@ -1,3 +1,3 @@
-mod lab;
+pub mod lab;
 #[cfg(test)]
 mod tests;
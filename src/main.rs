@@ -0,0 +1,499 @@
+use bio_lab::lab::Lab;
+use bio_lab::lab::analytics::{AnalyticsSettings, SequenceStats};
+use bio_lab::lab::config::LabConfig;
+use bio_lab::lab::evolution::{Evolution, EvolutionSettings, property_fitness};
+use bio_lab::lab::fm_index::GenomeIndex;
+use bio_lab::lab::motif::{CodonPattern, Motif, NucleotidePattern};
+use bio_lab::lab::mutation::{ChromosomeMutation, substitute_random};
+use bio_lab::lab::patch::GenomePatch;
+use bio_lab::lab::render::GenomeView;
+use bio_lab::lab::render::phenotype_svg::phenotype_svg;
+use bio_lab::lab::reproduction::cross;
+use bio_lab::lab::session::{
+    Session, codon_view, gene_view, motif_match_view, phenotype_view, protein_view,
+};
+use bio_lab::lab::types::codon_sequence::CodonSequence;
+use bio_lab::lab::types::genome::Genome;
+use bio_lab::lab::types::genome_format::GenomeFormat;
+use bio_lab::lab::types::nucleotide_sequence::NucleotideSequence;
+use clap::{Args, Parser, Subcommand};
+use rustyline::DefaultEditor;
+use rustyline::error::ReadlineError;
+use std::error::Error;
+use std::path::{Path, PathBuf};
+
+/// Generate, inspect and evolve genomes.
+///
+/// Genome files are read and written as packed bytes, FASTA (`.fa`, `.fasta`) or one line of ATGC
+/// per chromosome (`.txt`, `.atgc`), chosen by the file extension unless a format is given.
+#[derive(Debug, Parser)]
+#[command(name = "bio-lab", version)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Generate a random genome.
+    Random {
+        #[arg(long, default_value_t = 1)]
+        chromosomes: usize,
+        /// Nucleotides per chromosome.
+        #[arg(long, default_value_t = 300)]
+        length: u64,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Show the codons and the protein of every chromosome.
+    Translate {
+        #[command(flatten)]
+        input: Input,
+        /// Reading frame offset (0-2).
+        #[arg(long, default_value_t = 0, value_parser = clap::value_parser!(u8).range(0..=2))]
+        frame: u8,
+    },
+    /// List the genes found using a lab config and the resulting phenotype.
+    Inspect {
+        #[command(flatten)]
+        input: Input,
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Also draw the phenotype as SVG image.
+        #[arg(long)]
+        svg: Option<PathBuf>,
+    },
+    /// Print the genome colored with amino acids and genes, or write it as HTML report.
+    Render {
+        #[command(flatten)]
+        input: Input,
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Write a standalone HTML report instead of printing to the terminal.
+        #[arg(long)]
+        html: Option<PathBuf>,
+    },
+    /// Print nucleotide, codon and k-mer statistics of one or more genomes as JSON.
+    Stats {
+        /// All chromosomes of all genomes are analysed together.
+        #[arg(required = true)]
+        inputs: Vec<PathBuf>,
+        /// Print `metric,key,value` rows instead of JSON.
+        #[arg(long)]
+        csv: bool,
+        /// Nucleotides per GC content window, 0 to leave out the windows.
+        #[arg(long, default_value_t = AnalyticsSettings::default().window)]
+        window: usize,
+        #[arg(long, default_value_t = AnalyticsSettings::default().window_step)]
+        window_step: usize,
+        #[arg(long, default_value_t = AnalyticsSettings::default().kmer_size)]
+        kmer_size: usize,
+    },
+    /// Find a nucleotide pattern on both strands, or an amino acid pattern in the first frame.
+    Find {
+        #[command(flatten)]
+        input: Input,
+        /// IUPAC codes with `.` for any nucleotide, `[...]` sets and `{min,max}` repeats.
+        pattern: String,
+        /// Match one letter amino acid codes (`*` for stop, `x` for any) instead of nucleotides.
+        #[arg(long)]
+        protein: bool,
+    },
+    /// Build a substring index of every chromosome for `locate`.
+    Index {
+        #[command(flatten)]
+        input: Input,
+        /// Index file to write.
+        #[arg(short, long)]
+        output: PathBuf,
+    },
+    /// List every exact occurrence of a nucleotide sequence using an index built by `index`.
+    Locate {
+        index: PathBuf,
+        /// ATGC code to look up.
+        query: String,
+        /// Only print the amount of occurrences.
+        #[arg(long)]
+        count: bool,
+    },
+    /// Apply random point and structural mutations.
+    Mutate {
+        #[command(flatten)]
+        input: Input,
+        /// Probability of a substitution per nucleotide.
+        #[arg(long, default_value_t = 0.01)]
+        rate: f64,
+        /// Amount of random fusions, fissions and translocations.
+        #[arg(long, default_value_t = 0)]
+        structural: usize,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Cross two genomes with one random crossover point per chromosome.
+    Cross {
+        first: PathBuf,
+        second: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Convert a genome between packed bytes, FASTA and ATGC text.
+    Convert {
+        #[command(flatten)]
+        input: Input,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Print the patch turning the first genome into the second, annotated with codon effects.
+    Diff { original: PathBuf, target: PathBuf },
+    /// Apply a patch created by `diff` to a genome.
+    Patch {
+        #[command(flatten)]
+        input: Input,
+        patch: PathBuf,
+        #[command(flatten)]
+        output: Output,
+    },
+    /// Start an interactive shell keeping a lab and named genomes in memory.
+    Repl {
+        #[arg(long)]
+        config: Option<PathBuf>,
+    },
+    /// Evolve a population towards a phenotype property and write the fittest genome.
+    Evolve {
+        #[arg(long)]
+        config: Option<PathBuf>,
+        /// Phenotype property to maximize, summed over all nodes.
+        #[arg(long)]
+        fitness: String,
+        #[arg(long, default_value_t = 20)]
+        population: usize,
+        #[arg(long, default_value_t = 10)]
+        generations: usize,
+        /// Genome the population starts from, random genomes are used otherwise.
+        #[arg(long)]
+        seed: Option<PathBuf>,
+        #[arg(long, default_value_t = 1)]
+        chromosomes: usize,
+        #[arg(long, default_value_t = 600)]
+        length: u64,
+        #[arg(long, default_value_t = EvolutionSettings::default().mutation_rate)]
+        mutation_rate: f64,
+        #[command(flatten)]
+        output: Output,
+    },
+}
+
+#[derive(Debug, Args)]
+struct Input {
+    input: PathBuf,
+    #[arg(long, value_parser = parse_format)]
+    from: Option<GenomeFormat>,
+}
+
+impl Input {
+    fn load(&self) -> Result<Genome, Box<dyn Error>> {
+        load_genome(&self.input, self.from)
+    }
+}
+
+#[derive(Debug, Args)]
+struct Output {
+    /// Written to stdout as FASTA if missing.
+    #[arg(long, short)]
+    output: Option<PathBuf>,
+    #[arg(long, value_parser = parse_format)]
+    to: Option<GenomeFormat>,
+}
+
+impl Output {
+    fn write(&self, genome: &Genome) -> Result<(), Box<dyn Error>> {
+        match &self.output {
+            Some(path) => {
+                let format = self.to.unwrap_or_else(|| GenomeFormat::from_path(path));
+                genome.save_as(path, format)?;
+            }
+            None => match self.to.unwrap_or(GenomeFormat::Fasta) {
+                GenomeFormat::Bytes => return Err("packed bytes need an output file".into()),
+                format => print!("{}", String::from_utf8(format.encode(genome))?),
+            },
+        }
+        Ok(())
+    }
+}
+
+fn parse_format(value: &str) -> Result<GenomeFormat, String> {
+    match value {
+        "bytes" => Ok(GenomeFormat::Bytes),
+        "text" => Ok(GenomeFormat::Text),
+        _ => GenomeFormat::from_extension(value)
+            .ok_or_else(|| format!("unknown format '{}', use bytes, fasta or text", value)),
+    }
+}
+
+fn load_genome(path: &Path, format: Option<GenomeFormat>) -> Result<Genome, Box<dyn Error>> {
+    let format = format.unwrap_or_else(|| GenomeFormat::from_path(path));
+    Ok(Genome::load_as(path, format)?)
+}
+
+fn load_lab(config: &Option<PathBuf>) -> Result<Lab, Box<dyn Error>> {
+    let config = match config {
+        Some(path) => LabConfig::load(path)?,
+        None => LabConfig::default(),
+    };
+    Ok(Lab::with_builtin_genes(config))
+}
+
+fn main() {
+    if let Err(error) = run(Cli::parse()) {
+        eprintln!("error: {}", error);
+        std::process::exit(1);
+    }
+}
+
+fn run(cli: Cli) -> Result<(), Box<dyn Error>> {
+    match cli.command {
+        Command::Random {
+            chromosomes,
+            length,
+            output,
+        } => output.write(&Genome::random(chromosomes, length)),
+        Command::Translate { input, frame } => {
+            for chromosome in input.load()?.get_chromosomes() {
+                let nucleotides = chromosome.get_sequence().get_sequence();
+                let start = (frame as usize).min(nucleotides.len());
+                let codons = CodonSequence::from_nucleotides(&nucleotides[start..]);
+                println!(">{}", chromosome.get_name());
+                println!("codons:  {}", codon_view(&codons));
+                println!("protein: {}", protein_view(&codons));
+            }
+            Ok(())
+        }
+        Command::Inspect { input, config, svg } => {
+            let lab = load_lab(&config)?;
+            let genome = input.load()?;
+            for gene in lab.read_genome_genes(&genome) {
+                println!("{}", gene_view(&gene));
+            }
+            let phenotype = lab.express_genome(&genome);
+            println!("{}", phenotype_view(&phenotype));
+            if let Some(path) = svg {
+                std::fs::write(path, phenotype_svg(&phenotype))?;
+            }
+            Ok(())
+        }
+        Command::Render {
+            input,
+            config,
+            html,
+        } => {
+            let lab = load_lab(&config)?;
+            let view = GenomeView::new(&lab, &input.load()?);
+            match html {
+                Some(path) => {
+                    std::fs::write(&path, view.to_html(&input.input.display().to_string()))?
+                }
+                None => print!("{}", view.to_ansi()),
+            }
+            Ok(())
+        }
+        Command::Stats {
+            inputs,
+            csv,
+            window,
+            window_step,
+            kmer_size,
+        } => {
+            let genomes = inputs
+                .iter()
+                .map(|path| load_genome(path, None))
+                .collect::<Result<Vec<_>, _>>()?;
+            let settings = AnalyticsSettings {
+                window,
+                window_step,
+                kmer_size,
+            };
+            let stats = SequenceStats::of_population(&genomes, &settings);
+            match csv {
+                true => print!("{}", stats.to_csv()),
+                false => println!("{}", stats.to_json()),
+            }
+            Ok(())
+        }
+        Command::Find {
+            input,
+            pattern,
+            protein,
+        } => {
+            let genome = input.load()?;
+            if protein {
+                let pattern = CodonPattern::parse(&pattern)?;
+                for chromosome in genome.get_chromosomes() {
+                    let codons =
+                        CodonSequence::from_nucleotides(chromosome.get_sequence().get_sequence());
+                    for range in pattern.find_in_codons(&codons) {
+                        println!(
+                            "{}: codons {}..{} {}",
+                            chromosome.get_name(),
+                            range.start,
+                            range.end,
+                            protein_view(&CodonSequence::new(
+                                codons.get_sequence()[range.clone()].to_vec(),
+                                Vec::new()
+                            ))
+                        );
+                    }
+                }
+            } else {
+                let motif = Motif::Pattern(NucleotidePattern::parse(&pattern)?);
+                for chromosome in genome.get_chromosomes() {
+                    let nucleotides = chromosome.get_sequence().get_sequence();
+                    for found in motif.find_on_both_strands(nucleotides) {
+                        println!("{}", motif_match_view(chromosome.get_name(), &found));
+                    }
+                }
+            }
+            Ok(())
+        }
+        Command::Index { input, output } => {
+            let genome = input.load()?;
+            GenomeIndex::new(&genome).save(&output)?;
+            Ok(())
+        }
+        Command::Locate {
+            index,
+            query,
+            count,
+        } => {
+            let index = GenomeIndex::load(&index)?;
+            let query = NucleotideSequence::from_code_string(&query)
+                .map_err(|code| format!("invalid nucleotide '{}' in query", code))?;
+            if count {
+                println!("{}", index.count(query.get_sequence()));
+            } else {
+                for (chromosome, position) in index.locate(query.get_sequence()) {
+                    println!("{}: {}", chromosome, position);
+                }
+            }
+            Ok(())
+        }
+        Command::Mutate {
+            input,
+            rate,
+            structural,
+            output,
+        } => {
+            let mut genome = input.load()?;
+            let substitutions = substitute_random(&mut genome, rate);
+            let mut applied = 0;
+            for _ in 0..structural {
+                if let Some(mutation) = ChromosomeMutation::random(&genome)
+                    && mutation.apply(&mut genome).is_ok()
+                {
+                    applied += 1;
+                }
+            }
+            eprintln!(
+                "{} substitutions, {} structural mutations",
+                substitutions, applied
+            );
+            output.write(&genome)
+        }
+        Command::Cross {
+            first,
+            second,
+            output,
+        } => {
+            let first = load_genome(&first, None)?;
+            let second = load_genome(&second, None)?;
+            output.write(&cross(&first, &second))
+        }
+        Command::Convert { input, output } => output.write(&input.load()?),
+        Command::Diff { original, target } => {
+            let original = load_genome(&original, None)?;
+            let target = load_genome(&target, None)?;
+            print!(
+                "{}",
+                GenomePatch::between(&original, &target).to_annotated_text(&original)
+            );
+            Ok(())
+        }
+        Command::Patch {
+            input,
+            patch,
+            output,
+        } => {
+            let patch = GenomePatch::from_text(&std::fs::read_to_string(patch)?)?;
+            output.write(&patch.apply(&input.load()?)?)
+        }
+        Command::Repl { config } => repl(Session::new(load_lab(&config)?)),
+        Command::Evolve {
+            config,
+            fitness,
+            population,
+            generations,
+            seed,
+            chromosomes,
+            length,
+            mutation_rate,
+            output,
+        } => {
+            let lab = load_lab(&config)?;
+            let genomes = match &seed {
+                Some(path) => vec![load_genome(path, None)?; population],
+                None => (0..population)
+                    .map(|_| Genome::random(chromosomes, length))
+                    .collect(),
+            };
+            if genomes.is_empty() {
+                return Err("population must not be empty".into());
+            }
+
+            let settings = EvolutionSettings {
+                mutation_rate,
+                ..EvolutionSettings::default()
+            };
+            let fitness = property_fitness(&fitness);
+            let mut evolution = Evolution::new(genomes, settings);
+            for _ in 0..generations {
+                let stats = evolution.step(&lab, &fitness);
+                eprintln!(
+                    "generation {}: best {:.3}, mean {:.3}",
+                    stats.generation, stats.best_fitness, stats.mean_fitness
+                );
+            }
+
+            let best = evolution
+                .get_best(&lab, &fitness)
+                .expect("Population is not empty");
+            output.write(&best)
+        }
+    }
+}
+
+fn repl(mut session: Session) -> Result<(), Box<dyn Error>> {
+    let mut editor = DefaultEditor::new()?;
+    println!("bio-lab shell, type 'help' for the commands and 'exit' to leave");
+
+    loop {
+        let line = match editor.readline("bio-lab> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted | ReadlineError::Eof) => return Ok(()),
+            Err(error) => return Err(error.into()),
+        };
+        let _ = editor.add_history_entry(line.as_str());
+
+        match line.trim() {
+            "exit" | "quit" => return Ok(()),
+            line => match session.execute(line) {
+                Ok(output) if output.is_empty() => {}
+                Ok(output) => println!("{}", output),
+                Err(error) => eprintln!("error: {}", error),
+            },
+        }
+    }
+}
+
+// The library's tests live in `src/tests.rs`, which this crate root can't declare as `tests`
+#[cfg(test)]
+#[path = "tests/test_main.rs"]
+mod test_main;
@@ -1,3 +1,66 @@
+use crate::lab::config::LabConfig;
+use crate::lab::snapshot::GeneTypeSnapshot;
+use crate::lab::types::codon::{Codon, CodonType};
+use crate::lab::types::gene::{Gene, GeneProperties};
+use crate::lab::types::gene_type::GeneTypeTrait;
+use std::path::PathBuf;
+
+mod test_analytics;
+mod test_archive;
 mod test_codon_sequence;
+mod test_codon_value_config;
+mod test_config;
+mod test_diff;
+mod test_dominance;
+mod test_evolution;
+mod test_fm_index;
+mod test_gene_id_trie;
+mod test_gene_type;
+mod test_genome;
+mod test_genome_format;
 mod test_lab;
+mod test_motif;
 mod test_nucleotide_sequence;
+mod test_phenotype_svg;
+mod test_protein;
+mod test_regulatory_network;
+mod test_render;
+mod test_sequence_serde;
+mod test_session;
+mod test_snapshot;
+mod test_splicing;
+mod test_transcription;
+
+fn get_default_config() -> LabConfig {
+    let path = PathBuf::from("./data/config/default_lab_config.yaml");
+    LabConfig::load(&path).unwrap()
+}
+
+/// Gene type with a configurable id, saved in snapshots under the `test` tag.
+#[derive(Debug, Clone)]
+struct TestGeneType(Vec<CodonType>);
+
+impl GeneTypeTrait for TestGeneType {
+    fn name(&self) -> &str {
+        "test"
+    }
+
+    fn id_sequence(&self) -> Vec<CodonType> {
+        self.0.clone()
+    }
+
+    fn parse_codon_sequence(&self, _sequence: &[Codon], _config: &LabConfig) -> Gene {
+        Gene::new(self.clone_boxed(), 0, 1.0, GeneProperties::new())
+    }
+
+    fn clone_boxed(&self) -> Box<dyn GeneTypeTrait> {
+        Box::new(self.clone())
+    }
+
+    fn snapshot(&self) -> Option<GeneTypeSnapshot> {
+        Some(GeneTypeSnapshot::new(
+            "test",
+            serde_yaml::to_value(&self.0).unwrap(),
+        ))
+    }
+}
@@ -0,0 +1,90 @@
+use crate::lab::analytics::{
+    AnalyticsSettings, SequenceStats, gc_content, kmer_spectrum, shannon_entropy,
+    windowed_gc_content,
+};
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+
+fn sequence(code: &str) -> NucleotideSequence {
+    NucleotideSequence::from_code_string(code).unwrap()
+}
+
+#[test]
+fn test_nucleotide_statistics() {
+    let nucleotides = sequence("GGCCATAT");
+    let nucleotides = nucleotides.get_sequence();
+
+    assert_eq!(gc_content(nucleotides), 0.5);
+    assert_eq!(gc_content(&[]), 0.0);
+    assert_eq!(
+        windowed_gc_content(nucleotides, 4, 2),
+        [(0, 1.0), (2, 0.5), (4, 0.0)]
+    );
+    assert_eq!(windowed_gc_content(nucleotides, 20, 2), [(0, 0.5)]);
+    assert!(windowed_gc_content(nucleotides, 0, 2).is_empty());
+
+    let spectrum = kmer_spectrum(nucleotides, 2);
+    assert_eq!(spectrum["AT"], 2);
+    assert_eq!(spectrum["GG"], 1);
+    assert_eq!(spectrum.values().sum::<usize>(), 7);
+
+    assert_eq!(shannon_entropy([2, 2, 2, 2]), 2.0);
+    assert_eq!(shannon_entropy([5, 0]), 0.0);
+    assert_eq!(shannon_entropy([]), 0.0);
+}
+
+#[test]
+fn test_sequence_stats() {
+    // Frame 0: ATG TAA TAG, frame 1: TGT AAT AG, frame 2: GTA ATA G
+    let codons = CodonSequence::from_nucleotides(sequence("ATGTAATAGC").get_sequence());
+    let stats = SequenceStats::of_codons(&codons, &AnalyticsSettings::default());
+
+    assert_eq!(stats.length, 10);
+    assert_eq!(stats.nucleotide_counts["A"], 4);
+    assert_eq!(stats.codon_usage["ATG"], 1.0 / 3.0);
+    assert_eq!(stats.amino_acid_composition["Stop"], 2.0 / 3.0);
+    assert_eq!(stats.stop_codon_density, [2.0 / 3.0, 0.0, 0.0]);
+    assert_eq!(stats.kmer_counts.values().sum::<usize>(), 8);
+    assert_eq!(stats.gc_windows.len(), 1);
+
+    let random = SequenceStats::of(
+        NucleotideSequence::random(20000).get_sequence(),
+        &AnalyticsSettings::default(),
+    );
+    assert!((random.gc_content - 0.5).abs() < 0.05);
+    assert!(random.entropy > 1.99);
+    assert!(random.kmer_entropy > 5.9);
+}
+
+#[test]
+fn test_population_stats_export() {
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new("1", sequence("GGGG")))
+        .unwrap();
+    genome
+        .add_chromosome(Chromosome::new("2", sequence("AAAA")))
+        .unwrap();
+    let settings = AnalyticsSettings {
+        window: 2,
+        window_step: 2,
+        kmer_size: 1,
+    };
+    let stats = SequenceStats::of_population(&[genome.clone(), genome], &settings);
+
+    assert_eq!(stats.sequences, 4);
+    assert_eq!(stats.gc_content, 0.5);
+    assert_eq!(stats.entropy, 1.0);
+    assert_eq!(stats.gc_windows.len(), 8);
+    assert_eq!(stats.gc_windows[2].sequence, 1);
+
+    let restored: SequenceStats = serde_json::from_str(&stats.to_json()).unwrap();
+    assert_eq!(restored, stats);
+
+    let csv = stats.to_csv();
+    assert!(csv.starts_with("metric,key,value\nsequences,,4\nlength,,16\n"));
+    assert!(csv.contains("\ngc_window,1:2,0\n"));
+    assert!(csv.contains("\nkmer_count,G,8\n"));
+    assert!(csv.ends_with("kmer_entropy,,1\n"));
+}
@@ -0,0 +1,125 @@
+use crate::lab::archive::PopulationArchive;
+use crate::lab::error::ArchiveError;
+use crate::lab::mutation::{ChromosomeMutation, substitute_random};
+use crate::lab::types::genome::Genome;
+use std::path::PathBuf;
+
+fn get_members(reference: &Genome, count: usize) -> Vec<Genome> {
+    (0..count)
+        .map(|index| {
+            let mut genome = reference.clone();
+            substitute_random(&mut genome, 0.002);
+            if index % 5 == 0
+                && let Some(mutation) = ChromosomeMutation::random(&genome)
+            {
+                mutation.apply(&mut genome).unwrap();
+            }
+            genome
+        })
+        .collect()
+}
+
+fn get_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bio_lab_archive_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn test_archive_roundtrip() {
+    let dir = get_dir("roundtrip");
+    let path = dir.join("population.blpa");
+    let reference = Genome::random(3, 2000);
+    let members = get_members(&reference, 40);
+
+    let mut archive = PopulationArchive::create(&path, reference.clone()).unwrap();
+    for (index, member) in members.iter().enumerate() {
+        assert_eq!(archive.append(member).unwrap(), index);
+    }
+    assert_eq!(archive.get(17).unwrap(), members[17]);
+    assert_eq!(archive.get(3).unwrap(), members[3]);
+
+    let mut reopened = PopulationArchive::open(&path).unwrap();
+    assert_eq!(reopened.get_reference(), &reference);
+    assert_eq!(reopened.len(), members.len());
+    let restored: Vec<Genome> = reopened.iter().map(Result::unwrap).collect();
+    assert_eq!(restored, members);
+
+    reopened.append(&reference).unwrap();
+    assert_eq!(reopened.get(40).unwrap(), reference);
+    assert!(reopened.get_patch(40).unwrap().is_empty());
+    assert!(matches!(
+        reopened.get(41),
+        Err(ArchiveError::OutOfRange {
+            index: 41,
+            length: 41
+        })
+    ));
+
+    let archive_size = std::fs::metadata(&path).unwrap().len() as usize;
+    let raw_size: usize = members.iter().map(|member| member.to_bytes().len()).sum();
+    assert!(archive_size * 5 < raw_size);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_damaged_archive() {
+    let dir = get_dir("damaged");
+    let path = dir.join("population.blpa");
+    let reference = Genome::random(2, 500);
+    let members = get_members(&reference, 3);
+
+    let mut archive = PopulationArchive::create(&path, reference).unwrap();
+    for member in &members {
+        archive.append(member).unwrap();
+    }
+    drop(archive);
+
+    // An interrupted append loses only the last member, the next append replaces it
+    let length = std::fs::metadata(&path).unwrap().len();
+    let file = std::fs::OpenOptions::new().write(true).open(&path).unwrap();
+    file.set_len(length - 2).unwrap();
+    drop(file);
+    let mut archive = PopulationArchive::open(&path).unwrap();
+    assert_eq!(archive.len(), 2);
+    archive.append(&members[2]).unwrap();
+    let restored: Vec<Genome> = PopulationArchive::open(&path)
+        .unwrap()
+        .iter()
+        .map(Result::unwrap)
+        .collect();
+    assert_eq!(restored, members);
+
+    // Header byte of the reference's first packed sequence
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes[24] = 7;
+    std::fs::write(&path, &bytes).unwrap();
+    assert!(matches!(
+        PopulationArchive::open(&path),
+        Err(ArchiveError::InvalidData(_))
+    ));
+
+    // A raw record substituting at the largest varint position of chromosome `1`
+    let reference = Genome::random(1, 10);
+    drop(PopulationArchive::create(&path, reference).unwrap());
+    let mut payload = vec![1, 0, 1, b'1', 1, 0];
+    payload.extend_from_slice(&[0xff; 9]);
+    payload.extend_from_slice(&[0x01, 0b0001]);
+    let mut record = ((payload.len() + 1) as u32).to_le_bytes().to_vec();
+    record.push(0);
+    record.extend_from_slice(&payload);
+    let mut bytes = std::fs::read(&path).unwrap();
+    bytes.extend_from_slice(&record);
+    std::fs::write(&path, &bytes).unwrap();
+    let mut archive = PopulationArchive::open(&path).unwrap();
+    assert!(matches!(archive.get(0), Err(ArchiveError::InvalidData(_))));
+
+    std::fs::write(&path, b"not an archive").unwrap();
+    assert!(matches!(
+        PopulationArchive::open(&path),
+        Err(ArchiveError::InvalidData(_))
+    ));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,62 @@
+use crate::lab::config::codon_value_config::CodonValueConfig;
+use crate::lab::types::codon::{Codon, CodonLEU};
+use crate::tests::get_default_config;
+
+#[test]
+fn test_variant_overrides() {
+    let yaml = "
+met: 1.0
+stop: 1.0
+ala: 1.0
+arg: 1.0
+asn: 1.0
+asp: 1.0
+cys: 1.0
+gln: 1.0
+glu: 1.0
+gly: 1.0
+his: 1.0
+ile: 1.0
+leu: 3.0
+lys: 1.0
+phe: 1.0
+pro: 1.0
+ser: 1.0
+thr: 1.0
+trp: 1.0
+tyr: 1.0
+val: 1.0
+variants:
+  leu:
+    CTG: 2.0
+";
+    let config: CodonValueConfig<f64> = serde_yaml::from_str(yaml).unwrap();
+
+    assert_eq!(config.get_value(Codon::Leu(CodonLEU::CTG)), 2.0);
+    assert_eq!(config.get_value(Codon::Leu(CodonLEU::TTA)), 3.0);
+    assert_eq!(config.get_type_value(Codon::Leu(CodonLEU::CTG)), 3.0);
+    assert!(config.invalid_variants().is_empty());
+
+    let reserialized = serde_yaml::to_string(&config).unwrap();
+    assert_eq!(
+        serde_yaml::from_str::<CodonValueConfig<f64>>(&reserialized).unwrap(),
+        config
+    );
+}
+
+#[test]
+fn test_invalid_variants() {
+    let mut config = get_default_config().codon_strength;
+    assert!(!serde_yaml::to_string(&config).unwrap().contains("variants"));
+
+    let leu = config.variants.entry("leu".to_string()).or_default();
+    leu.insert("ATG".to_string(), 2.0);
+    leu.insert("TTA".to_string(), 2.0);
+    config
+        .variants
+        .entry("xyz".to_string())
+        .or_default()
+        .insert("TTT".to_string(), 2.0);
+
+    assert_eq!(config.invalid_variants(), vec!["leu.ATG", "xyz.TTT"]);
+}
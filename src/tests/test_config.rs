@@ -0,0 +1,175 @@
+use crate::lab::config::LabConfig;
+use crate::lab::config::config_format::ConfigFormat;
+use crate::lab::config::splicing_config::{MAX_TRANSCRIPTS, SplicingConfig};
+use crate::lab::error::ConfigError;
+use crate::lab::types::dominance::Dominance;
+use std::path::Path;
+
+const PARTIAL: &str = "./data/config/test/partial_lab_config.yaml";
+const OVERRIDE: &str = "./data/config/test/override_lab_config.yaml";
+const PARTIAL_TOML: &str = "./data/config/test/partial_lab_config.toml";
+const OVERRIDE_JSON: &str = "./data/config/test/override_lab_config.json";
+const INVALID: &str = "./data/config/test/invalid_lab_config.yaml";
+const SCHEMA: &str = "./data/config/schemas/lab_config.json";
+
+#[test]
+fn test_partial_config() {
+    let config = LabConfig::load(Path::new(PARTIAL)).unwrap();
+    assert_eq!(config.codon_strength.ala, 2.0);
+    assert_eq!(config.codon_strength.leu, 0.5);
+    assert_eq!(config.codon_strength.met, 1.0);
+    assert_eq!(config.protein, LabConfig::default().protein);
+}
+
+#[test]
+fn test_layered_config() {
+    let config = LabConfig::load_layers(&[Path::new(PARTIAL), Path::new(OVERRIDE)]).unwrap();
+    assert_eq!(config.codon_strength.ala, 2.0);
+    assert_eq!(config.codon_strength.leu, 3.0);
+    assert_eq!(config.dominance.get("color"), Some(&Dominance::Dominant));
+}
+
+#[test]
+fn test_mixed_format_layers() {
+    let config =
+        LabConfig::load_layers(&[Path::new(PARTIAL_TOML), Path::new(OVERRIDE_JSON)]).unwrap();
+    assert_eq!(config.codon_strength.ala, 2.0);
+    assert_eq!(config.codon_strength.leu, 3.0);
+    assert_eq!(config.dominance.get("color"), Some(&Dominance::Dominant));
+}
+
+#[test]
+fn test_save_and_load_roundtrip() {
+    let mut config = LabConfig::load(Path::new(PARTIAL)).unwrap();
+    config
+        .dominance
+        .insert("shape".to_string(), Dominance::Recessive);
+    let dir = std::env::temp_dir().join(format!("bio_lab_config_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for format in ConfigFormat::all() {
+        let path = dir.join(format!("lab_config.{}", format.get_extension()));
+        config.save(&path).unwrap();
+        assert_eq!(ConfigFormat::from_path(&path), Some(format));
+        assert_eq!(LabConfig::load(&path).unwrap(), config);
+
+        // An explicit format wins over the extension.
+        let other = dir.join(format!("{}.cfg", format.get_extension()));
+        config.save_with_format(&other, format).unwrap();
+        assert_eq!(LabConfig::load_with_format(&other, format).unwrap(), config);
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_embedded_toml_config() {
+    #[derive(serde::Deserialize)]
+    struct ServiceConfig {
+        name: String,
+        #[serde(deserialize_with = "LabConfig::deserialize_layer")]
+        lab: LabConfig,
+    }
+
+    let content = "name = \"lab-service\"\n\n[lab.codon_strength]\nala = 2.0\n";
+    let service: ServiceConfig = toml::from_str(content).unwrap();
+    assert_eq!(service.name, "lab-service");
+    assert_eq!(service.lab.codon_strength.ala, 2.0);
+    assert_eq!(service.lab.codon_strength.met, 1.0);
+
+    let section = "[codon_strength]\nala = 2.0\n";
+    let config = LabConfig::from_content(section, ConfigFormat::Toml).unwrap();
+    assert_eq!(config, service.lab);
+}
+
+#[test]
+fn test_env_overrides() {
+    let vars = [
+        (
+            "BIO_LAB__CODON_STRENGTH__ALA".to_string(),
+            "4.5".to_string(),
+        ),
+        (
+            "BIO_LAB__SPLICING__MAX_TRANSCRIPTS".to_string(),
+            "3".to_string(),
+        ),
+        (
+            "BIO_LAB__CODON_STRENGTH__VARIANTS__LEU__CTG".to_string(),
+            "2.5".to_string(),
+        ),
+        ("OTHER__CODON_STRENGTH__ALA".to_string(), "0.0".to_string()),
+    ];
+    let config = LabConfig::load_layers_with_vars(&[Path::new(PARTIAL)], "BIO_LAB", vars).unwrap();
+    assert_eq!(config.codon_strength.ala, 4.5);
+    assert_eq!(config.splicing.unwrap().max_transcripts, 3);
+    assert_eq!(config.codon_strength.variants["leu"]["CTG"], 2.5);
+
+    let vars = [(
+        "BIO_LAB__CODON_STRENGTH__ALA".to_string(),
+        "high".to_string(),
+    )];
+    let Err(ConfigError::Validation(problems)) =
+        LabConfig::load_layers_with_vars(&[], "BIO_LAB", vars)
+    else {
+        panic!("Expected a schema violation");
+    };
+    assert_eq!(problems.len(), 1);
+    assert!(problems[0].contains("/codon_strength/ala"));
+}
+
+#[test]
+fn test_config_errors() {
+    assert!(matches!(
+        LabConfig::load(Path::new("./data/config/test/missing.yaml")),
+        Err(ConfigError::Io { .. })
+    ));
+
+    let Err(ConfigError::Validation(problems)) = LabConfig::load(Path::new(INVALID)) else {
+        panic!("Expected a validation error");
+    };
+    assert_eq!(problems.len(), 2);
+
+    let mut config = LabConfig::default();
+    config.codon_strength.gly = f64::NAN;
+    assert!(config.validate().is_err());
+
+    let config = LabConfig {
+        splicing: Some(SplicingConfig {
+            max_transcripts: MAX_TRANSCRIPTS + 1,
+            ..SplicingConfig::default()
+        }),
+        ..LabConfig::default()
+    };
+    let Err(ConfigError::Validation(problems)) = config.validate() else {
+        panic!("Expected a validation error");
+    };
+    assert!(problems[0].contains("max_transcripts"));
+}
+
+#[test]
+fn test_unknown_fields_are_reported() {
+    let vars = [("BIO_LAB__PROTEIN__COLOR".to_string(), "1.0".to_string())];
+    let Err(ConfigError::Validation(problems)) =
+        LabConfig::load_layers_with_vars(&[], "BIO_LAB", vars)
+    else {
+        panic!("Expected a schema violation");
+    };
+    assert!(problems[0].contains("/protein"));
+    assert!(problems[0].contains("color"));
+}
+
+#[test]
+fn test_checked_in_schema_is_up_to_date() {
+    let generated = LabConfig::json_schema();
+    if std::env::var_os("BIO_LAB_UPDATE_SCHEMA").is_some() {
+        let json = serde_json::to_string_pretty(&generated).unwrap();
+        std::fs::write(SCHEMA, json + "\n").unwrap();
+    }
+
+    let checked_in: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(SCHEMA).unwrap()).unwrap();
+    assert_eq!(
+        checked_in, generated,
+        "Schema is outdated, regenerate it with `BIO_LAB_UPDATE_SCHEMA=1 cargo test`"
+    );
+}
@@ -0,0 +1,205 @@
+use crate::lab::diff::{AminoAcidChange, EditEffect, SequenceDiff, SequenceEdit};
+use crate::lab::error::PatchError;
+use crate::lab::mutation::{ChromosomeMutation, substitute_random};
+use crate::lab::patch::{ChromosomePatch, GenomePatch};
+use crate::lab::types::codon::CodonType;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide::*;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+
+fn sequence(code: &str) -> NucleotideSequence {
+    NucleotideSequence::from_code_string(code).unwrap()
+}
+
+fn effect_of(original: &str, target: &str) -> Vec<EditEffect> {
+    SequenceDiff::between(&sequence(original), &sequence(target))
+        .annotate(&sequence(original))
+        .into_iter()
+        .map(|(_, effect)| effect)
+        .collect()
+}
+
+#[test]
+fn test_simple_edits() {
+    let diff = SequenceDiff::between(&sequence("ATGGCATAA"), &sequence("ATGGAATAA"));
+    assert_eq!(
+        diff.get_edits(),
+        [SequenceEdit::Substitution {
+            position: 4,
+            from: C,
+            to: A
+        }]
+    );
+
+    let diff = SequenceDiff::between(&sequence("ATGGCATAA"), &sequence("ATGGCCCATAA"));
+    assert!(matches!(
+        diff.get_edits(),
+        [SequenceEdit::Insertion { nucleotides, .. }] if nucleotides.len() == 2
+    ));
+
+    let diff = SequenceDiff::between(&sequence("ATGGCATAA"), &sequence("ATGTAA"));
+    assert!(matches!(
+        diff.get_edits(),
+        [SequenceEdit::Deletion { nucleotides, .. }] if nucleotides.len() == 3
+    ));
+
+    let original = sequence("AAAAAAAAAAGATTACAGGCCCCCCCCCCC");
+    let target = sequence("AAAAAAAAAAGCCTGTAATCCCCCCCCCCC");
+    let diff = SequenceDiff::between(&original, &target);
+    // The outer G and C of the segment are complementary, so only the inner part is inverted.
+    assert_eq!(
+        diff.get_edits(),
+        [SequenceEdit::Inversion {
+            position: 11,
+            length: 8
+        }]
+    );
+    assert_eq!(diff.apply(&original).unwrap(), target);
+}
+
+#[test]
+fn test_diff_applies_to_random_mutations() {
+    for _ in 0..20 {
+        let mut genome = Genome::random(2, 200);
+        let original = genome.clone();
+        substitute_random(&mut genome, 0.05);
+        for _ in 0..3 {
+            if let Some(mutation) = ChromosomeMutation::random(&genome) {
+                let _ = mutation.apply(&mut genome);
+            }
+        }
+
+        let patch = GenomePatch::between(&original, &genome);
+        assert_eq!(patch.apply(&original).unwrap(), genome);
+        assert_eq!(GenomePatch::from_text(&patch.to_text()).unwrap(), patch);
+    }
+}
+
+#[test]
+fn test_diff_of_unrelated_sequences() {
+    let original = NucleotideSequence::random(4000);
+    let target = NucleotideSequence::random(3000);
+    let diff = SequenceDiff::between(&original, &target);
+    assert_eq!(diff.apply(&original).unwrap(), target);
+    assert!(SequenceDiff::between(&original, &original).is_empty());
+}
+
+#[test]
+fn test_codon_effects() {
+    assert_eq!(
+        effect_of("ATGGCATAA", "ATGGAATAA"),
+        [EditEffect::Missense(vec![AminoAcidChange {
+            codon: 1,
+            from: CodonType::Ala,
+            to: CodonType::Glu
+        }])]
+    );
+    assert_eq!(
+        effect_of("ATGGCATAA", "ATGGCGTAA"),
+        [EditEffect::Synonymous]
+    );
+    assert!(matches!(
+        effect_of("ATGTGGTAA", "ATGTGATAA").as_slice(),
+        [EditEffect::Nonsense(_)]
+    ));
+    assert!(matches!(
+        effect_of("ATGTGGTAA", "ATGTGGTTA").as_slice(),
+        [EditEffect::StopLost(_)]
+    ));
+    assert_eq!(
+        effect_of("ATGGCATAA", "ATGGCAGTAA"),
+        [EditEffect::Frameshift { codon: 2 }]
+    );
+    assert_eq!(
+        effect_of("ATGGCATAA", "ATGTAA"),
+        [EditEffect::InFrameDeletion {
+            codon: 1,
+            codons: 1
+        }]
+    );
+    assert_eq!(effect_of("ATGGCATA", "ATGGCATT"), [EditEffect::NonCoding]);
+}
+
+#[test]
+fn test_patch_text() {
+    let text = "\
+bio-lab patch 1
+# reviewed
+@ 1
+S 4 C>A  # missense
+I 9 GG
+V 0 3
+- 2
++ 3 1 ATG
+";
+    let patch = GenomePatch::from_text(text).unwrap();
+    assert_eq!(patch.get_changes().len(), 3);
+    assert!(matches!(
+        &patch.get_changes()[0],
+        ChromosomePatch::Modify { diff, .. } if diff.get_edits().len() == 3
+    ));
+
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new("1", sequence("ATGGCATAA")))
+        .unwrap();
+    genome
+        .add_chromosome(Chromosome::new("2", sequence("TT")))
+        .unwrap();
+    let patched = patch.apply(&genome).unwrap();
+    assert_eq!(
+        patched
+            .get_chromosome("1")
+            .unwrap()
+            .get_sequence()
+            .get_code_string(),
+        "CATGAATAAGG"
+    );
+    assert!(patched.get_chromosome("2").is_none());
+    assert!(patched.get_chromosome("3").is_some());
+
+    let annotated = patch.to_annotated_text(&genome);
+    assert!(annotated.contains("S 4 C>A  # missense Ala1Glu"));
+    assert_eq!(GenomePatch::from_text(&annotated).unwrap(), patch);
+}
+
+#[test]
+fn test_patch_errors() {
+    assert!(matches!(
+        GenomePatch::from_text("@ 1\n"),
+        Err(PatchError::Parse { line: 1, .. })
+    ));
+    assert!(matches!(
+        GenomePatch::from_text("bio-lab patch 1\nS 1 A>G\n"),
+        Err(PatchError::Parse { line: 2, .. })
+    ));
+    assert!(matches!(
+        GenomePatch::from_text("bio-lab patch 1\n@ 1\nS 1 A>X\n"),
+        Err(PatchError::Parse { line: 3, .. })
+    ));
+
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new("1", sequence("ATG")))
+        .unwrap();
+    let conflict = GenomePatch::from_text("bio-lab patch 1\n@ 1\nS 1 A>G\n").unwrap();
+    assert!(matches!(
+        conflict.apply(&genome),
+        Err(PatchError::Conflict { position: 1, .. })
+    ));
+    let overflow =
+        GenomePatch::from_text("bio-lab patch 1\n@ 1\nV 5 18446744073709551615\n").unwrap();
+    assert!(matches!(
+        overflow.apply(&genome),
+        Err(PatchError::Conflict { position: 5, .. })
+    ));
+    let beyond = GenomePatch::from_text("bio-lab patch 1\n@ 1\nV 2 100\nS 7 A>G\n").unwrap();
+    let annotated = beyond.to_annotated_text(&genome);
+    assert!(annotated.contains("V 2 100  # non-coding"));
+    assert!(annotated.contains("S 7 A>G  # non-coding"));
+    let unknown = GenomePatch::from_text("bio-lab patch 1\n@ 2\nS 1 A>G\n").unwrap();
+    assert!(matches!(
+        unknown.apply(&genome),
+        Err(PatchError::UnknownChromosome(_))
+    ));
+}
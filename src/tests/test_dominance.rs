@@ -0,0 +1,132 @@
+use crate::lab::Lab;
+use crate::lab::config::splicing_config::SplicingConfig;
+use crate::lab::types::codon::{Codon, CodonGLY, CodonLYS, CodonPRO, CodonSTOP};
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::dominance::Dominance;
+use crate::lab::types::gene::{Gene, GeneProperties};
+use crate::lab::types::gene_type::{GeneType, GeneTypeTrait};
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::organism::Organism;
+use crate::lab::types::phenotype::Phenotype;
+use crate::tests::get_default_config;
+
+fn allele(strength: f32, hue: f64) -> Gene {
+    let mut properties = GeneProperties::new();
+    properties.insert("hue".to_string(), hue);
+    Gene::new(Box::new(GeneType::Color), 0, strength, properties)
+}
+
+fn gene_codons(gene_type: GeneType, value: Codon) -> Vec<Codon> {
+    let mut codons = vec![Codon::Met];
+    codons.extend(
+        gene_type
+            .id_sequence()
+            .into_iter()
+            .map(Codon::random_from_type),
+    );
+    codons.extend([Codon::Lys(CodonLYS::AAA), Codon::Lys(CodonLYS::AAA), value]);
+    codons.push(Codon::Stop(CodonSTOP::TAG));
+    codons
+}
+
+fn homolog(genes: &[Vec<Codon>]) -> NucleotideSequence {
+    let codons = CodonSequence::new(genes.concat(), vec![]);
+    NucleotideSequence::new(codons.to_nucleotides())
+}
+
+#[test]
+fn test_resolve() {
+    let alleles = || vec![allele(1.0, 0.0), allele(3.0, 100.0)];
+
+    let dominant = Dominance::Dominant.resolve(alleles(), 2, 2);
+    assert_eq!(dominant.len(), 1);
+    assert_eq!(dominant[0].get_property("hue"), Some(100.0));
+
+    assert_eq!(Dominance::CoDominant.resolve(alleles(), 2, 2).len(), 2);
+    assert_eq!(Dominance::Recessive.resolve(alleles(), 2, 2).len(), 1);
+    assert!(
+        Dominance::Recessive
+            .resolve(vec![allele(1.0, 0.0)], 1, 2)
+            .is_empty()
+    );
+
+    let blended = Dominance::Incomplete.resolve(alleles(), 2, 2);
+    assert_eq!(blended.len(), 1);
+    let hue = blended[0].get_property("hue").unwrap();
+    assert!((hue - 80.79).abs() < 0.01);
+    assert_eq!(blended[0].get_strength(), 2.0);
+
+    let wrapped = Dominance::Incomplete.resolve(vec![allele(1.0, 0.0), allele(1.0, 350.0)], 2, 2);
+    let hue = wrapped[0].get_property("hue").unwrap();
+    assert!((hue - 355.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_codominant_hue() {
+    let alleles = Dominance::CoDominant.resolve(vec![allele(1.0, 350.0), allele(1.0, 30.0)], 2, 2);
+    let phenotype = Phenotype::from_genes(&alleles);
+    let hue = phenotype.get_node(0).unwrap().get_property("hue").unwrap();
+    assert!((hue - 10.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_express_diploid() {
+    let lab = Lab::with_builtin_genes(get_default_config());
+    let organism = Organism::diploid(
+        homolog(&[
+            gene_codons(GeneType::Color, Codon::Pro(CodonPRO::CCC)),
+            gene_codons(GeneType::Behaviour, Codon::Pro(CodonPRO::CCC)),
+        ]),
+        homolog(&[gene_codons(GeneType::Color, Codon::Lys(CodonLYS::AAA))]),
+    );
+
+    let phenotype = lab.express_organism(&organism);
+    let node = phenotype.get_node(0).unwrap();
+    assert_eq!(node.get_property("hue"), Some(0.0));
+    assert_eq!(node.get_property("aggression"), None);
+}
+
+#[test]
+fn test_express_alternative_transcripts() {
+    let spliced = loop {
+        // Random id codons must not form a splice site within the gene
+        let sequence = homolog(&[gene_codons(GeneType::Color, Codon::Gly(CodonGLY::GGG))]);
+        if !sequence.get_code_string().contains("GTAAGT") {
+            break sequence;
+        }
+    };
+    let other = homolog(&[gene_codons(GeneType::Color, Codon::Pro(CodonPRO::CCC))]);
+
+    let mut config = get_default_config();
+    config.splicing = Some(SplicingConfig {
+        max_transcripts: 2,
+        ..SplicingConfig::default()
+    });
+    let lab = Lab::with_builtin_genes(config);
+    let introns = format!("{}GTAAGTTTTCAGCCGTAAGTTTTCAGCC", spliced.get_code_string());
+    let organism = Organism::diploid(
+        NucleotideSequence::from_code_string(&introns).unwrap(),
+        other.clone(),
+    );
+    let reference = Organism::diploid(spliced, other);
+
+    assert_eq!(lab.splice(&organism.get_homologs()[0]).len(), 2);
+    let hue = |organism: &Organism| {
+        let phenotype = lab.express_organism(organism);
+        phenotype.get_node(0).unwrap().get_property("hue").unwrap()
+    };
+    assert_eq!(hue(&organism), hue(&reference));
+}
+
+#[test]
+fn test_dominance_config_override() {
+    let mut config = get_default_config();
+    config
+        .dominance
+        .insert("behaviour".to_string(), Dominance::Dominant);
+    let lab = Lab::with_builtin_genes(config);
+
+    assert_eq!(lab.get_dominance(&GeneType::Behaviour), Dominance::Dominant);
+    assert_eq!(lab.get_dominance(&GeneType::Shape), Dominance::Dominant);
+    assert_eq!(lab.get_dominance(&GeneType::Color), Dominance::Incomplete);
+}
@@ -0,0 +1,83 @@
+use crate::lab::Lab;
+use crate::lab::evolution::{Evolution, EvolutionSettings, property_fitness};
+use crate::lab::mutation::substitute_random;
+use crate::lab::reproduction::cross;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide::*;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::tests::get_default_config;
+
+fn uniform_genome(name: &str, nucleotide: crate::lab::types::nucleotide::Nucleotide) -> Genome {
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new(
+            name,
+            NucleotideSequence::new(vec![nucleotide; 20]),
+        ))
+        .unwrap();
+    genome
+}
+
+#[test]
+fn test_cross() {
+    let mut first = uniform_genome("1", A);
+    first
+        .add_chromosome(Chromosome::new("x", NucleotideSequence::new(vec![G])))
+        .unwrap();
+    let second = uniform_genome("1", T);
+
+    let offspring = cross(&first, &second);
+    let code = offspring
+        .get_chromosome("1")
+        .unwrap()
+        .get_sequence()
+        .get_code_string();
+    assert_eq!(code.len(), 20);
+    assert!(code.trim_start_matches('A').chars().all(|c| c == 'T'));
+    assert!(offspring.get_chromosome("x").is_some());
+}
+
+#[test]
+fn test_substitute_random() {
+    let mut genome = uniform_genome("1", A);
+    assert_eq!(substitute_random(&mut genome, 0.0), 0);
+    assert_eq!(substitute_random(&mut genome, 1.0), 20);
+    let code = genome
+        .get_chromosome("1")
+        .unwrap()
+        .get_sequence()
+        .get_code_string();
+    assert!(!code.contains('A'));
+}
+
+#[test]
+fn test_evolution_keeps_the_fittest() {
+    let lab = Lab::with_builtin_genes(get_default_config());
+    let fitness = property_fitness("size");
+    let population = (0..6).map(|_| Genome::random(1, 300)).collect();
+    let mut evolution = Evolution::new(population, EvolutionSettings::default());
+
+    let first = evolution.step(&lab, &fitness);
+    let second = evolution.step(&lab, &fitness);
+    assert_eq!(evolution.get_generation(), 2);
+    assert_eq!(evolution.get_population().len(), 6);
+    assert!(first.best_fitness >= first.mean_fitness);
+    // The elite is carried over, so the best fitness never decreases.
+    assert!(second.best_fitness >= first.best_fitness);
+}
+
+#[test]
+fn test_evolution_with_nan_rates() {
+    let lab = Lab::with_builtin_genes(get_default_config());
+    let fitness = property_fitness("size");
+    let population = vec![uniform_genome("1", A), uniform_genome("1", T)];
+    let settings = EvolutionSettings {
+        mutation_rate: f64::NAN,
+        chromosome_mutation_rate: f64::NAN,
+        ..EvolutionSettings::default()
+    };
+    let mut evolution = Evolution::new(population, settings);
+
+    evolution.step(&lab, &fitness);
+    assert_eq!(evolution.get_population().len(), 2);
+}
@@ -0,0 +1,172 @@
+use crate::lab::error::GenomeError;
+use crate::lab::fm_index::{FmIndex, GenomeIndex};
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use rand::Rng;
+
+fn nucleotides(code: &str) -> Vec<Nucleotide> {
+    NucleotideSequence::from_code_string(code)
+        .unwrap()
+        .get_sequence()
+        .to_vec()
+}
+
+fn naive_locate(text: &[Nucleotide], query: &[Nucleotide]) -> Vec<usize> {
+    if query.is_empty() {
+        return Vec::new();
+    }
+    text.windows(query.len())
+        .enumerate()
+        .filter(|(_, window)| *window == query)
+        .map(|(position, _)| position)
+        .collect()
+}
+
+fn assert_matches_scan(text: &[Nucleotide], index: &FmIndex, query: &[Nucleotide]) {
+    let expected = naive_locate(text, query);
+    assert_eq!(index.count(query), expected.len());
+    assert_eq!(index.locate(query), expected);
+}
+
+#[test]
+fn test_fm_index_random_sequences() {
+    let mut rng = rand::rng();
+    for length in [0, 1, 2, 31, 32, 33, 64, 65, 200, 3000] {
+        let sequence = NucleotideSequence::random(length);
+        let text = sequence.get_sequence();
+        let index = FmIndex::new(&sequence);
+        assert_eq!(index.len(), text.len());
+
+        for _ in 0..50 {
+            let query_length = rng.random_range(1..=8);
+            let query: Vec<Nucleotide> = if text.len() >= query_length && rng.random_bool(0.5) {
+                let start = rng.random_range(0..=text.len() - query_length);
+                text[start..start + query_length].to_vec()
+            } else {
+                NucleotideSequence::random(query_length as u64)
+                    .get_sequence()
+                    .to_vec()
+            };
+            assert_matches_scan(text, &index, &query);
+        }
+        assert_matches_scan(text, &index, text);
+    }
+}
+
+#[test]
+fn test_fm_index_repetitive_sequences() {
+    for code in [
+        "AAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAAA",
+        "ATATATATATATATATATATATATATATATATATATATATATATATAT",
+    ] {
+        let text = nucleotides(code);
+        let index = FmIndex::from_nucleotides(&text);
+        for query in ["A", "AA", "AAAAA", "AT", "TA", "ATA", "TAT", "G", "AAG"] {
+            assert_matches_scan(&text, &index, &nucleotides(query));
+        }
+    }
+}
+
+#[test]
+fn test_fm_index_queries() {
+    let text = nucleotides("GATTACAGATTACA");
+    let index = FmIndex::from_nucleotides(&text);
+
+    assert_eq!(index.locate(&nucleotides("GATTACA")), vec![0, 7]);
+    assert_eq!(index.locate(&nucleotides("ACA")), vec![4, 11]);
+    assert_eq!(index.count(&nucleotides("A")), 6);
+    assert!(index.contains(&nucleotides("CAG")));
+    assert!(!index.contains(&nucleotides("GG")));
+    assert!(!index.contains(&nucleotides("GATTACAGATTACAG")));
+    // Empty queries match nothing
+    assert_eq!(index.count(&[]), 0);
+    assert!(index.locate(&[]).is_empty());
+}
+
+#[test]
+fn test_fm_index_bytes() {
+    let sequence = NucleotideSequence::random(500);
+    let index = FmIndex::new(&sequence);
+    let bytes = index.to_bytes();
+    // 2 bits per nucleotide after the header
+    assert_eq!(bytes.len(), 12 + 501usize.div_ceil(32) * 8);
+
+    let restored = FmIndex::from_bytes(&bytes).unwrap();
+    assert_eq!(restored, index);
+
+    assert!(matches!(
+        FmIndex::from_bytes(&bytes[..bytes.len() - 1]),
+        Err(GenomeError::InvalidBytes(_))
+    ));
+    assert!(matches!(
+        FmIndex::from_bytes(b"BLGA"),
+        Err(GenomeError::InvalidBytes(_))
+    ));
+
+    let mut sentinel = bytes;
+    sentinel[8..12].copy_from_slice(&1000u32.to_le_bytes());
+    assert!(FmIndex::from_bytes(&sentinel).is_err());
+
+    // The sentinel's slot in the BWT has to hold an A
+    let mut bytes = FmIndex::from_nucleotides(&nucleotides("ACGTTGCAACGT")).to_bytes();
+    let sentinel_row = u32::from_le_bytes(bytes[8..12].try_into().unwrap()) as usize;
+    bytes[12 + sentinel_row / 4] |= (Nucleotide::C as u8) << (2 * (sentinel_row % 4));
+    assert!(matches!(
+        FmIndex::from_bytes(&bytes),
+        Err(GenomeError::InvalidBytes(_))
+    ));
+}
+
+#[test]
+fn test_fm_index_serde() {
+    let sequence = NucleotideSequence::from_code_string("ATGCATGCAATT").unwrap();
+    let index = FmIndex::new(&sequence);
+
+    let json = serde_json::to_string(&index).unwrap();
+    assert!(json.starts_with("\"base64:"));
+    let restored: FmIndex = serde_json::from_str(&json).unwrap();
+    assert_eq!(restored, index);
+    assert!(serde_json::from_str::<FmIndex>("\"ATGC\"").is_err());
+}
+
+#[test]
+fn test_genome_index() {
+    let mut genome = Genome::new();
+    for (name, code) in [("one", "ATGCCATGA"), ("two", "CCATGG"), ("three", "")] {
+        genome
+            .add_chromosome(Chromosome::new(
+                name,
+                NucleotideSequence::from_code_string(code).unwrap(),
+            ))
+            .unwrap();
+    }
+
+    let index = GenomeIndex::new(&genome);
+    assert_eq!(index.count(&nucleotides("ATG")), 3);
+    assert_eq!(
+        index.locate(&nucleotides("ATG")),
+        vec![("one", 0), ("one", 5), ("two", 2)]
+    );
+    assert_eq!(index.get_index("two").unwrap().len(), 6);
+    assert!(index.get_index("four").is_none());
+
+    let path = std::env::temp_dir().join(format!("bio_lab_genome_index_{}", std::process::id()));
+    index.save(&path).unwrap();
+    let loaded = GenomeIndex::load(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, index);
+
+    let bytes = index.to_bytes();
+    assert!(GenomeIndex::from_bytes(&bytes[..bytes.len() - 3]).is_err());
+
+    let random = Genome::random(3, 2000);
+    let index = GenomeIndex::new(&random);
+    let query = &random.get_chromosomes()[1].get_sequence().get_sequence()[100..112];
+    let expected: usize = random
+        .get_chromosomes()
+        .iter()
+        .map(|chromosome| naive_locate(chromosome.get_sequence().get_sequence(), query).len())
+        .sum();
+    assert_eq!(index.count(query), expected);
+}
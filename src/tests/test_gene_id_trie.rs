@@ -0,0 +1,82 @@
+use crate::lab::gene_id_trie::GeneIdTrie;
+use crate::lab::types::codon::{Codon, CodonType};
+use crate::tests::TestGeneType;
+
+fn codons(codon_types: &[CodonType]) -> Vec<Codon> {
+    codon_types
+        .iter()
+        .map(|codon_type| Codon::random_from_type(*codon_type))
+        .collect()
+}
+
+fn get_trie() -> GeneIdTrie {
+    GeneIdTrie::new(vec![
+        Box::new(TestGeneType(vec![CodonType::Ala, CodonType::Arg])),
+        Box::new(TestGeneType(vec![
+            CodonType::Gly,
+            CodonType::Ala,
+            CodonType::Arg,
+            CodonType::Val,
+        ])),
+        Box::new(TestGeneType(vec![CodonType::Val])),
+    ])
+}
+
+#[test]
+fn test_find_prefix() {
+    let trie = get_trie();
+
+    let (gene_type, length) = trie
+        .find_prefix(&codons(&[CodonType::Ala, CodonType::Arg, CodonType::Lys]))
+        .unwrap();
+    assert_eq!(
+        gene_type.id_sequence(),
+        vec![CodonType::Ala, CodonType::Arg]
+    );
+    assert_eq!(length, 2);
+
+    assert!(trie.find_prefix(&codons(&[CodonType::Ala])).is_none());
+    assert!(
+        trie.find_prefix(&codons(&[CodonType::Lys, CodonType::Val]))
+            .is_none()
+    );
+}
+
+#[test]
+fn test_find_all() {
+    let trie = get_trie();
+    let sequence = codons(&[
+        CodonType::Met,
+        CodonType::Gly,
+        CodonType::Ala,
+        CodonType::Arg,
+        CodonType::Val,
+        CodonType::Ala,
+        CodonType::Arg,
+    ]);
+
+    let found: Vec<(usize, usize, Vec<CodonType>)> = trie
+        .find_all(&sequence)
+        .iter()
+        .map(|m| (m.position, m.length, m.gene_type.id_sequence()))
+        .collect();
+
+    assert_eq!(
+        found,
+        vec![
+            (2, 2, vec![CodonType::Ala, CodonType::Arg]),
+            (
+                1,
+                4,
+                vec![
+                    CodonType::Gly,
+                    CodonType::Ala,
+                    CodonType::Arg,
+                    CodonType::Val
+                ]
+            ),
+            (4, 1, vec![CodonType::Val]),
+            (5, 2, vec![CodonType::Ala, CodonType::Arg]),
+        ]
+    );
+}
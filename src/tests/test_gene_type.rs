@@ -0,0 +1,61 @@
+use crate::lab::types::codon::{Codon, CodonASN, CodonGLU, CodonLYS, CodonPRO};
+use crate::lab::types::gene_type::{GeneType, GeneTypeTrait};
+use crate::tests::get_default_config;
+use std::collections::HashSet;
+
+#[test]
+fn test_builtin_ids_are_unique() {
+    let ids: HashSet<_> = GeneType::all()
+        .iter()
+        .map(|gene_type| gene_type.id_sequence())
+        .collect();
+    assert_eq!(ids.len(), GeneType::all().len());
+}
+
+#[test]
+fn test_parse_color() {
+    let config = get_default_config();
+    let sequence = [
+        Codon::Lys(CodonLYS::AAA),
+        Codon::Asn(CodonASN::AAT),
+        Codon::Pro(CodonPRO::CCC),
+        Codon::Lys(CodonLYS::AAA),
+        Codon::Pro(CodonPRO::CCC),
+    ];
+
+    let gene = GeneType::Color.parse_codon_sequence(&sequence, &config);
+    assert_eq!(gene.get_node_id(), 1);
+    assert_eq!(gene.get_strength(), 1.0);
+    assert_eq!(gene.get_property("hue"), Some(360.0));
+    assert_eq!(gene.get_property("saturation"), Some(0.0));
+    assert_eq!(gene.get_property("lightness"), Some(1.0));
+}
+
+#[test]
+fn test_parse_metabolism_protein() {
+    let config = get_default_config();
+    let sequence = [
+        Codon::Lys(CodonLYS::AAA),
+        Codon::Lys(CodonLYS::AAA),
+        Codon::Lys(CodonLYS::AAA),
+        Codon::Glu(CodonGLU::GAA),
+    ];
+
+    let gene = GeneType::Metabolism.parse_codon_sequence(&sequence, &config);
+    let hydrophobicity = gene.get_property("hydrophobicity").unwrap();
+    assert!((hydrophobicity - (-3.9 * 3.0 - 3.5) / 4.0).abs() < 1e-9);
+    assert_eq!(gene.get_property("charge"), Some(2.0));
+}
+
+#[test]
+fn test_parse_truncated_payload() {
+    let config = get_default_config();
+    for gene_type in GeneType::all() {
+        let gene = gene_type.parse_codon_sequence(&[], &config);
+        assert_eq!(gene.get_strength(), 0.0);
+        assert_eq!(
+            gene.get_properties().is_empty(),
+            gene.get_regulation().is_some()
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use crate::lab::error::GenomeError;
+use crate::lab::mutation::{ChromosomeMutation, substitute_random};
+use crate::lab::types::genome::{Chromosome, Genome, MAX_NAME_LENGTH};
+use crate::lab::types::nucleotide::Nucleotide::*;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+
+fn get_genome() -> Genome {
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new(
+            "1",
+            NucleotideSequence::new(vec![A, A, T, T]),
+        ))
+        .unwrap();
+    genome
+        .add_chromosome(Chromosome::new("2", NucleotideSequence::new(vec![G, G, C])))
+        .unwrap();
+    genome
+}
+
+fn code_string(genome: &Genome, name: &str) -> String {
+    genome
+        .get_chromosome(name)
+        .unwrap()
+        .get_sequence()
+        .get_code_string()
+}
+
+#[test]
+fn test_byte_parsing() {
+    for count in 0..5 {
+        let genome = Genome::random(count, count as u64 * 7);
+        let bytes = genome.to_bytes();
+        assert_eq!(Genome::from_bytes(&bytes).unwrap(), genome);
+    }
+
+    let bytes = get_genome().to_bytes();
+    assert!(Genome::from_bytes(&bytes[..bytes.len() - 1]).is_err());
+    assert!(Genome::from_bytes(b"nope").is_err());
+
+    // Header byte of the first chromosome's packed sequence
+    let mut damaged = bytes;
+    damaged[15] = 7;
+    assert!(matches!(
+        Genome::from_bytes(&damaged),
+        Err(GenomeError::InvalidBytes(_))
+    ));
+}
+
+#[test]
+fn test_name_length() {
+    let mut genome = Genome::new();
+    let name = "n".repeat(MAX_NAME_LENGTH + 1);
+    assert!(matches!(
+        genome.add_chromosome(Chromosome::new(name, NucleotideSequence::new(vec![A]))),
+        Err(GenomeError::NameTooLong(65536))
+    ));
+
+    let name = "n".repeat(MAX_NAME_LENGTH);
+    genome
+        .add_chromosome(Chromosome::new(name, NucleotideSequence::new(vec![A])))
+        .unwrap();
+    assert_eq!(Genome::from_bytes(&genome.to_bytes()).unwrap(), genome);
+}
+
+#[test]
+fn test_fusion_and_fission() {
+    let mut genome = get_genome();
+    ChromosomeMutation::Fusion {
+        first: "1".to_string(),
+        second: "2".to_string(),
+    }
+    .apply(&mut genome)
+    .unwrap();
+    assert_eq!(genome.get_chromosomes().len(), 1);
+    assert_eq!(code_string(&genome, "1"), "AATTGGC");
+
+    ChromosomeMutation::Fission {
+        chromosome: "1".to_string(),
+        position: 2,
+        new_name: "1.1".to_string(),
+    }
+    .apply(&mut genome)
+    .unwrap();
+    assert_eq!(code_string(&genome, "1"), "AA");
+    assert_eq!(code_string(&genome, "1.1"), "TTGGC");
+}
+
+#[test]
+fn test_translocation() {
+    let mut genome = get_genome();
+    ChromosomeMutation::Translocation {
+        source: "1".to_string(),
+        start: 1,
+        length: 2,
+        target: "2".to_string(),
+        position: 1,
+    }
+    .apply(&mut genome)
+    .unwrap();
+    assert_eq!(code_string(&genome, "1"), "AT");
+    assert_eq!(code_string(&genome, "2"), "GATGC");
+
+    let invalid = ChromosomeMutation::Translocation {
+        source: "1".to_string(),
+        start: 0,
+        length: 1,
+        target: "2".to_string(),
+        position: 10,
+    };
+    assert!(invalid.apply(&mut genome).is_err());
+    assert_eq!(code_string(&genome, "1"), "AT");
+
+    let overflow = ChromosomeMutation::Translocation {
+        source: "1".to_string(),
+        start: 1,
+        length: usize::MAX,
+        target: "2".to_string(),
+        position: 0,
+    };
+    assert!(matches!(
+        overflow.apply(&mut genome),
+        Err(GenomeError::OutOfBounds { length: 2, .. })
+    ));
+}
+
+#[test]
+fn test_random_mutations_apply() {
+    for _ in 0..100 {
+        let mut genome = Genome::random(3, 10);
+        let total_length = genome.total_length();
+        let mutation = ChromosomeMutation::random(&genome).unwrap();
+        mutation.apply(&mut genome).unwrap();
+        assert_eq!(genome.total_length(), total_length);
+    }
+}
+
+#[test]
+fn test_substitution_rates() {
+    let mut genome = Genome::random(2, 50);
+    let original = genome.clone();
+    assert_eq!(substitute_random(&mut genome, f64::NAN), 0);
+    assert_eq!(substitute_random(&mut genome, -1.0), 0);
+    assert_eq!(genome, original);
+    assert_eq!(substitute_random(&mut genome, 2.0), 100);
+}
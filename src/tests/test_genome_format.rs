@@ -0,0 +1,61 @@
+use crate::lab::error::GenomeError;
+use crate::lab::types::genome::Genome;
+use crate::lab::types::genome_format::{FASTA_LINE_WIDTH, GenomeFormat};
+use std::path::Path;
+
+#[test]
+fn test_format_roundtrips() {
+    let genome = Genome::random(3, 150);
+    for format in [GenomeFormat::Bytes, GenomeFormat::Fasta, GenomeFormat::Text] {
+        let decoded = format.decode(&format.encode(&genome)).unwrap();
+        assert_eq!(decoded, genome);
+    }
+
+    let fasta = genome.to_fasta();
+    assert!(fasta.starts_with(">1\n"));
+    assert!(fasta.lines().all(|line| line.len() <= FASTA_LINE_WIDTH));
+    assert_eq!(
+        GenomeFormat::from_path(Path::new("genome.fasta")),
+        GenomeFormat::Fasta
+    );
+    assert_eq!(
+        GenomeFormat::from_path(Path::new("genome")),
+        GenomeFormat::Bytes
+    );
+}
+
+#[test]
+fn test_parse_fasta_and_text() {
+    let genome = Genome::from_fasta(">chr1 first\nATG\ncat\n; comment\n\n>chr2\nGG\n").unwrap();
+    let names: Vec<&str> = genome
+        .get_chromosomes()
+        .iter()
+        .map(|chromosome| chromosome.get_name())
+        .collect();
+    assert_eq!(names, ["chr1", "chr2"]);
+    assert_eq!(
+        genome
+            .get_chromosome("chr1")
+            .unwrap()
+            .get_sequence()
+            .get_code_string(),
+        "ATGCAT"
+    );
+
+    let genome = Genome::from_text("ATG\n\nGGC\n").unwrap();
+    assert_eq!(genome.get_chromosomes().len(), 2);
+    assert!(genome.get_chromosome("2").is_some());
+
+    assert!(matches!(
+        Genome::from_fasta(">1\nATG\nAXG\n"),
+        Err(GenomeError::InvalidText { line: 3, .. })
+    ));
+    assert!(matches!(
+        Genome::from_fasta("ATG\n"),
+        Err(GenomeError::InvalidText { line: 1, .. })
+    ));
+    assert!(matches!(
+        Genome::from_fasta(">1\nA\n>1\nT\n"),
+        Err(GenomeError::DuplicateChromosome(_))
+    ));
+}
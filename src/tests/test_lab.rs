@@ -1,13 +1,117 @@
+use crate::lab::Lab;
 use crate::lab::config::LabConfig;
-use std::path::PathBuf;
-
-fn get_default_config() -> LabConfig {
-    let path = PathBuf::from("./data/config/default_lab_config.yaml");
-    LabConfig::load(&path).unwrap()
-}
+use crate::lab::config::codon_value_table::CodonValueTable;
+use crate::lab::error::LabError;
+use crate::lab::types::codon::{Codon, CodonASN, CodonLYS, CodonPRO, CodonSTOP, CodonType};
+use crate::lab::types::gene_type::{GeneType, GeneTypeTrait};
+use crate::tests::{TestGeneType, get_default_config};
 
 #[test]
 fn test_config() {
     let config = get_default_config();
     assert_eq!(config.codon_strength.ala, 1.0);
 }
+
+#[test]
+fn test_register_gene() {
+    let mut lab = Lab::new(get_default_config());
+    lab.register_gene(Box::new(GeneType::Color)).unwrap();
+    lab.register_gene(Box::new(TestGeneType(vec![CodonType::Arg, CodonType::Ala])))
+        .unwrap();
+    lab.register_gene(Box::new(TestGeneType(vec![CodonType::Arg, CodonType::Arg])))
+        .unwrap();
+
+    assert!(lab.get_gene_type(&GeneType::Color.id_sequence()).is_some());
+    assert_eq!(lab.gene_types().count(), 3);
+    assert_eq!(lab.get_gene_id_trie().len(), 3);
+}
+
+#[test]
+fn test_register_gene_collisions() {
+    let mut lab = Lab::new(get_default_config());
+    lab.register_gene(Box::new(TestGeneType(vec![CodonType::Arg, CodonType::Ala])))
+        .unwrap();
+
+    assert_eq!(
+        lab.register_gene(Box::new(TestGeneType(vec![CodonType::Arg, CodonType::Ala]))),
+        Err(LabError::DuplicateGeneId(vec![
+            CodonType::Arg,
+            CodonType::Ala
+        ]))
+    );
+    assert_eq!(
+        lab.register_gene(Box::new(TestGeneType(vec![CodonType::Arg]))),
+        Err(LabError::AmbiguousGeneId {
+            id: vec![CodonType::Arg],
+            conflicting_id: vec![CodonType::Arg, CodonType::Ala],
+        })
+    );
+    assert!(
+        lab.register_gene(Box::new(TestGeneType(vec![
+            CodonType::Arg,
+            CodonType::Ala,
+            CodonType::Gly
+        ])))
+        .is_err()
+    );
+    assert_eq!(lab.gene_types().count(), 1);
+}
+
+#[test]
+fn test_read_genes() {
+    let lab = Lab::with_builtin_genes(get_default_config());
+    let mut codons = vec![Codon::Trp, Codon::Met];
+    codons.extend(
+        GeneType::Size
+            .id_sequence()
+            .into_iter()
+            .map(Codon::random_from_type),
+    );
+    codons.extend([
+        Codon::Lys(CodonLYS::AAA),
+        Codon::Asn(CodonASN::AAT),
+        Codon::Pro(CodonPRO::CCC),
+        Codon::Stop(CodonSTOP::TAA),
+        Codon::Met,
+        Codon::Met,
+    ]);
+
+    let genes = lab.read_genes(&codons);
+    assert_eq!(genes.len(), 1);
+    assert_eq!(genes[0].get_gene_type().name(), "size");
+    assert_eq!(genes[0].get_node_id(), 1);
+
+    let phenotype = lab.express(&codons);
+    assert_eq!(
+        phenotype.get_node(1).unwrap().get_property("size"),
+        Some(10.0)
+    );
+}
+
+#[test]
+fn test_codon_tables() {
+    let mut config = get_default_config();
+    let mut hue = config.codon_strength.clone();
+    hue.trp = 0.5;
+    config
+        .codon_tables
+        .insert("hue".to_string(), CodonValueTable::F64(hue));
+
+    let yaml = serde_yaml::to_string(&config).unwrap();
+    assert!(yaml.contains("type: f64"));
+    let config: LabConfig = serde_yaml::from_str(&yaml).unwrap();
+
+    assert_eq!(
+        config.get_f64_table("hue").unwrap().get_value(Codon::Trp),
+        0.5
+    );
+    assert!(config.get_i64_table("hue").is_none());
+    assert!(config.get_f64_table("size").is_none());
+    assert_eq!(
+        config
+            .get_codon_table("hue")
+            .unwrap()
+            .get_value_f64(Codon::Met),
+        1.0
+    );
+}
@@ -0,0 +1,131 @@
+use super::{Cli, Command, run};
+use bio_lab::lab::mutation::substitute_random;
+use bio_lab::lab::patch::GenomePatch;
+use bio_lab::lab::types::genome::Genome;
+use bio_lab::lab::types::genome_format::GenomeFormat;
+use clap::Parser;
+use std::path::{Path, PathBuf};
+
+fn get_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bio_lab_cli_{}_{}", name, std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn execute(args: &[&str]) {
+    let cli = Cli::try_parse_from([&["bio-lab"], args].concat()).unwrap();
+    run(cli).unwrap();
+}
+
+fn arg(path: &Path) -> &str {
+    path.to_str().unwrap()
+}
+
+#[test]
+fn test_parse_arguments() {
+    let cli =
+        Cli::try_parse_from(["bio-lab", "convert", "a.fa", "--to", "text", "-o", "b"]).unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Convert { input, output }
+            if input.input == Path::new("a.fa")
+                && output.output.as_deref() == Some(Path::new("b"))
+                && output.to == Some(GenomeFormat::Text)
+    ));
+
+    let cli = Cli::try_parse_from(["bio-lab", "diff", "a.fa", "b.fa"]).unwrap();
+    assert!(matches!(cli.command, Command::Diff { .. }));
+
+    let cli = Cli::try_parse_from([
+        "bio-lab",
+        "evolve",
+        "--fitness",
+        "size",
+        "--population",
+        "4",
+    ])
+    .unwrap();
+    assert!(matches!(
+        cli.command,
+        Command::Evolve { fitness, population: 4, generations: 10, .. } if fitness == "size"
+    ));
+
+    assert!(Cli::try_parse_from(["bio-lab", "evolve"]).is_err());
+    assert!(Cli::try_parse_from(["bio-lab", "translate", "a.fa", "--frame", "3"]).is_err());
+    assert!(Cli::try_parse_from(["bio-lab", "convert", "a.fa", "--to", "png"]).is_err());
+}
+
+#[test]
+fn test_convert() {
+    let dir = get_dir("convert");
+    let (fasta, text) = (dir.join("genome.fa"), dir.join("genome.txt"));
+    let genome = Genome::random(2, 50);
+    genome.save_as(&fasta, GenomeFormat::Fasta).unwrap();
+
+    execute(&["convert", arg(&fasta), "-o", arg(&text)]);
+    assert_eq!(Genome::load_as(&text, GenomeFormat::Text).unwrap(), genome);
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_diff_and_patch() {
+    let dir = get_dir("patch");
+    let (original_path, target_path) = (dir.join("original.fa"), dir.join("target.fa"));
+    let (patch_path, patched_path) = (dir.join("target.patch"), dir.join("patched.fa"));
+    let original = Genome::random(1, 120);
+    let mut target = original.clone();
+    substitute_random(&mut target, 0.1);
+    original
+        .save_as(&original_path, GenomeFormat::Fasta)
+        .unwrap();
+    target.save_as(&target_path, GenomeFormat::Fasta).unwrap();
+
+    execute(&["diff", arg(&original_path), arg(&target_path)]);
+
+    let patch = GenomePatch::between(&original, &target);
+    std::fs::write(&patch_path, patch.to_annotated_text(&original)).unwrap();
+    execute(&[
+        "patch",
+        arg(&original_path),
+        arg(&patch_path),
+        "-o",
+        arg(&patched_path),
+    ]);
+    assert_eq!(
+        Genome::load_as(&patched_path, GenomeFormat::Fasta).unwrap(),
+        target
+    );
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_evolve() {
+    let dir = get_dir("evolve");
+    let best = dir.join("best.fa");
+    execute(&[
+        "evolve",
+        "--fitness",
+        "size",
+        "--population",
+        "3",
+        "--generations",
+        "2",
+        "--length",
+        "90",
+        "-o",
+        arg(&best),
+    ]);
+    let genome = Genome::load_as(&best, GenomeFormat::Fasta).unwrap();
+    assert_eq!(genome.get_chromosomes()[0].get_sequence().len(), 90);
+
+    let empty = Cli::try_parse_from([
+        "bio-lab",
+        "evolve",
+        "--fitness",
+        "size",
+        "--population",
+        "0",
+    ]);
+    assert!(run(empty.unwrap()).is_err());
+    std::fs::remove_dir_all(&dir).unwrap();
+}
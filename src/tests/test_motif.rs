@@ -0,0 +1,179 @@
+use crate::lab::error::MotifError;
+use crate::lab::motif::{
+    CodonPattern, Motif, MotifMatch, NucleotidePattern, PositionWeightMatrix, Strand,
+};
+use crate::lab::types::codon::CodonType;
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use std::ops::Range;
+
+fn nucleotides(code: &str) -> Vec<crate::lab::types::nucleotide::Nucleotide> {
+    NucleotideSequence::from_code_string(code)
+        .unwrap()
+        .get_sequence()
+        .to_vec()
+}
+
+fn bounds(ranges: Vec<Range<usize>>) -> Vec<(usize, usize)> {
+    ranges
+        .iter()
+        .map(|range| (range.start, range.end))
+        .collect()
+}
+
+#[test]
+fn test_nucleotide_patterns() {
+    let sequence = nucleotides("GGTATAATCCTATATTGG");
+
+    let exact = NucleotidePattern::exact(&nucleotides("TATA"));
+    assert_eq!(bounds(exact.find(&sequence)), [(2, 6), (10, 14)]);
+    assert_eq!(NucleotidePattern::parse("tata").unwrap(), exact);
+
+    let iupac = NucleotidePattern::parse("TATAWT").unwrap();
+    assert_eq!(bounds(iupac.find(&sequence)), [(2, 8), (10, 16)]);
+    let set = NucleotidePattern::parse("TAT[A]AT").unwrap();
+    assert_eq!(bounds(set.find(&sequence)), [(2, 8)]);
+
+    // Gaps match as few nucleotides as possible
+    let gapped = NucleotidePattern::parse("GG .{2,12} CC").unwrap();
+    assert_eq!(gapped.min_length(), 6);
+    assert_eq!(bounds(gapped.find(&sequence)), [(0, 10)]);
+    assert!(
+        NucleotidePattern::parse("GGN{0,2}T")
+            .unwrap()
+            .find(&sequence)
+            .contains(&(0..3))
+    );
+}
+
+#[test]
+fn test_pattern_errors() {
+    for (pattern, position) in [
+        ("", 0),
+        ("TA[", 2),
+        ("TX", 1),
+        ("{2}A", 0),
+        ("A{3,1}", 1),
+        ("A{0,100000}", 1),
+    ] {
+        assert!(
+            matches!(
+                NucleotidePattern::parse(pattern),
+                Err(MotifError::Parse { position: found, .. }) if found == position
+            ),
+            "{}",
+            pattern
+        );
+    }
+    assert!(CodonPattern::parse("MB").is_err());
+}
+
+/// Whether the pattern, given as (allowed codes, min, max), matches all of the nucleotides.
+fn matches_fully(elements: &[(&str, usize, usize)], nucleotides: &str) -> bool {
+    let Some(((allowed, min, max), rest)) = elements.split_first() else {
+        return nucleotides.is_empty();
+    };
+    (*min..=*max).any(|count| {
+        count <= nucleotides.len()
+            && nucleotides[..count]
+                .chars()
+                .all(|code| allowed.contains(code))
+            && matches_fully(rest, &nucleotides[count..])
+    })
+}
+
+#[test]
+fn test_shortest_matches() {
+    let elements = [("A", 0, 2), ("AG", 1, 3), ("T", 0, 1), ("GC", 1, 2)];
+    let pattern = NucleotidePattern::parse("A{0,2}[AG]{1,3}T{0,1}[GC]{1,2}").unwrap();
+    for _ in 0..20 {
+        let sequence = NucleotideSequence::random(60);
+        let code = sequence.get_code_string();
+        let expected: Vec<(usize, usize)> = (0..code.len())
+            .filter_map(|start| {
+                (start + 1..=code.len())
+                    .find(|end| matches_fully(&elements, &code[start..*end]))
+                    .map(|end| (start, end))
+            })
+            .collect();
+        assert_eq!(bounds(pattern.find(sequence.get_sequence())), expected);
+    }
+
+    // Nested optional repeats stay linear in the sequence length
+    let pattern = NucleotidePattern::parse("N{0,30}N{0,30}N{0,30}G").unwrap();
+    let sequence = NucleotideSequence::random(20_000);
+    let matches = pattern.find(sequence.get_sequence());
+    assert!(matches.iter().all(|range| range.end - range.start <= 91));
+}
+
+#[test]
+fn test_both_strands() {
+    // TTA on the reverse strand is TAA
+    let sequence = nucleotides("TAACCCTTA");
+    let motif = Motif::Pattern(NucleotidePattern::parse("TAA").unwrap());
+
+    assert_eq!(motif.find(&sequence).len(), 1);
+    assert_eq!(
+        motif.find_on_both_strands(&sequence),
+        [
+            MotifMatch {
+                start: 0,
+                end: 3,
+                strand: Strand::Forward,
+                score: None
+            },
+            MotifMatch {
+                start: 6,
+                end: 9,
+                strand: Strand::Reverse,
+                score: None
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_position_weight_matrix() {
+    let sites = [
+        nucleotides("TATAAT"),
+        nucleotides("TATAAT"),
+        nucleotides("TACAAT"),
+    ];
+    let sites: Vec<&[_]> = sites.iter().map(Vec::as_slice).collect();
+    let matrix = PositionWeightMatrix::from_sites(&sites, 0.25).unwrap();
+    assert_eq!(matrix.len(), 6);
+
+    let consensus = nucleotides("TATAAT");
+    assert_eq!(matrix.score(&consensus), matrix.max_score());
+    assert!(matrix.score(&nucleotides("TACAAT")) < matrix.max_score());
+    assert!(matrix.score(&nucleotides("GCGCGC")) < 0.0);
+
+    let sequence = nucleotides("GCGTATAATGCGATTATAGC");
+    let motif = Motif::Matrix {
+        threshold: matrix.relative_threshold(0.9),
+        matrix: matrix.clone(),
+    };
+    let matches = motif.find_on_both_strands(&sequence);
+    assert_eq!(matches.len(), 2);
+    assert_eq!((matches[0].start, matches[0].strand), (3, Strand::Forward));
+    assert_eq!((matches[1].start, matches[1].strand), (12, Strand::Reverse));
+    assert_eq!(matches[0].score, Some(matrix.max_score()));
+
+    assert!(PositionWeightMatrix::from_sites(&[&consensus, &consensus[1..]], 1.0).is_err());
+    assert!(PositionWeightMatrix::new(vec![]).is_err());
+}
+
+#[test]
+fn test_codon_patterns() {
+    // ATG AAA CGT TAA GGG ATG TAG
+    let codons = CodonSequence::from_nucleotides(&nucleotides("ATGAAACGTTAAGGGATGTAG"));
+
+    let pattern = CodonPattern::parse("M[KR]x{0,3}*").unwrap();
+    assert_eq!(bounds(pattern.find_in_codons(&codons)), [(0, 4)]);
+    let any = CodonPattern::parse("m.{0,1}*").unwrap();
+    assert_eq!(bounds(any.find_in_codons(&codons)), [(5, 7)]);
+
+    let exact = CodonPattern::exact(&[CodonType::Stop, CodonType::Gly]);
+    assert_eq!(bounds(exact.find_in_codons(&codons)), [(3, 5)]);
+    assert_eq!(CodonType::from_symbol('k'), Some(CodonType::Lys));
+}
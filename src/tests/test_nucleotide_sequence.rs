@@ -7,5 +7,14 @@ fn test_byte_parsing() {
         let bytes = sequence.to_bytes();
         let initial_sequence = NucleotideSequence::from_bytes(&bytes);
         assert_eq!(sequence, initial_sequence);
+        assert_eq!(
+            NucleotideSequence::try_from_bytes(&bytes).unwrap(),
+            sequence
+        );
     }
+
+    assert!(NucleotideSequence::try_from_bytes(&[]).unwrap().is_empty());
+    assert!(NucleotideSequence::try_from_bytes(&[7, 0b11100100]).is_err());
+    assert!(NucleotideSequence::try_from_bytes(&[2]).is_err());
+    assert_eq!(NucleotideSequence::from_bytes(&[7, 0b11100100]).len(), 3);
 }
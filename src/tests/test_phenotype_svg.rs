@@ -0,0 +1,92 @@
+use crate::lab::render::phenotype_svg::{node_fill, node_outline, phenotype_svg};
+use crate::lab::types::gene::{Gene, GeneProperties};
+use crate::lab::types::gene_type::GeneType;
+use crate::lab::types::phenotype::Phenotype;
+
+fn gene(gene_type: GeneType, node_id: u64, properties: &[(&str, f64)]) -> Gene {
+    let properties: GeneProperties = properties
+        .iter()
+        .map(|(name, value)| (name.to_string(), *value))
+        .collect();
+    Gene::new(Box::new(gene_type), node_id, 1.0, properties)
+}
+
+fn extent(outline: &[(f64, f64)]) -> (f64, f64) {
+    outline.iter().fold((0.0, 0.0), |(width, height), (x, y)| {
+        (f64::max(width, x.abs()), f64::max(height, y.abs()))
+    })
+}
+
+#[test]
+fn test_node_appearance() {
+    let phenotype = Phenotype::from_genes(&[
+        gene(
+            GeneType::Color,
+            1,
+            &[("hue", 120.0), ("saturation", 1.0), ("lightness", 0.5)],
+        ),
+        gene(GeneType::Size, 2, &[("size", 2.0)]),
+        gene(
+            GeneType::Shape,
+            2,
+            &[("segments", 1.0), ("symmetry", 1.0), ("elongation", 4.0)],
+        ),
+    ]);
+    let colored = phenotype.get_node(1).unwrap();
+    let shaped = phenotype.get_node(2).unwrap();
+
+    assert_eq!(node_fill(colored), "hsl(120, 100%, 50%)");
+    assert_eq!(node_fill(shaped), "hsl(0, 0%, 60%)");
+
+    let (width, height) = extent(&node_outline(colored));
+    assert!((width - height).abs() < 1e-9);
+    let (width, height) = extent(&node_outline(shaped));
+    assert!((width / height - 4.0).abs() < 1e-6);
+    assert!(width > extent(&node_outline(colored)).0);
+}
+
+#[test]
+fn test_phenotype_svg() {
+    let phenotype = Phenotype::from_genes(&[
+        gene(
+            GeneType::Color,
+            7,
+            &[("hue", 10.0), ("saturation", 0.5), ("lightness", 0.5)],
+        ),
+        gene(GeneType::SensorRange, 7, &[("range", 10.0)]),
+        gene(
+            GeneType::Behaviour,
+            3,
+            &[
+                ("aggression", 1.0),
+                ("sociability", 0.0),
+                ("curiosity", 1.0),
+            ],
+        ),
+    ]);
+    let svg = phenotype_svg(&phenotype);
+
+    assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+    assert!(svg.ends_with("</svg>\n"));
+    assert_eq!(svg.matches("<polygon").count(), 2);
+    assert!(svg.contains("fill=\"hsl(10, 50%, 50%)\""));
+    assert!(svg.contains("stroke-dasharray"));
+    assert!(svg.contains("fill=\"#fff\""));
+    // Nodes are ordered by id
+    assert!(svg.find(">3</text>").unwrap() < svg.find(">7</text>").unwrap());
+
+    assert!(phenotype_svg(&Phenotype::default()).contains("no expressed nodes"));
+}
+
+#[test]
+fn test_extreme_properties() {
+    let phenotype = Phenotype::from_genes(&[
+        gene(GeneType::Color, 1, &[("hue", f64::NAN)]),
+        gene(GeneType::Speed, 1, &[("speed", 1e9)]),
+        gene(GeneType::Metabolism, 1, &[("efficiency", f64::NAN)]),
+    ]);
+    let svg = phenotype_svg(&phenotype);
+
+    assert!(!svg.contains("NaN"));
+    assert_eq!(svg.matches("<line").count(), 4);
+}
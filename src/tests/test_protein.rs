@@ -0,0 +1,37 @@
+use crate::lab::protein::{ProteinProperties, SecondaryStructure};
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::tests::get_default_config;
+
+fn codons(code: &str) -> CodonSequence {
+    let nucleotides = NucleotideSequence::from_code_string(code).unwrap();
+    CodonSequence::from_nucleotides(nucleotides.get_sequence())
+}
+
+#[test]
+fn test_protein_properties() {
+    let config = get_default_config();
+    // Lys Glu Ala Stop
+    let properties = codons("AAAGAAGCATAA").protein_properties(&config.protein);
+
+    assert_eq!(properties.length, 3);
+    assert!((properties.hydrophobicity - (-3.9 - 3.5 + 1.8) / 3.0).abs() < 1e-9);
+    assert_eq!(properties.charge, 0.0);
+    assert!((properties.mass - (128.1741 + 129.1155 + 71.0788 + 18.015)).abs() < 1e-9);
+    assert_eq!(properties.secondary_structure(), SecondaryStructure::Helix);
+}
+
+#[test]
+fn test_secondary_structure() {
+    let config = get_default_config();
+    // Val Ile Tyr
+    let sheet = codons("GTTATTTAT").protein_properties(&config.protein);
+    assert_eq!(sheet.secondary_structure(), SecondaryStructure::Sheet);
+
+    // Gly Pro Asn
+    let coil = codons("GGTCCTAAT").protein_properties(&config.protein);
+    assert_eq!(coil.secondary_structure(), SecondaryStructure::Coil);
+
+    let empty = ProteinProperties::from_codons(&[], &config.protein);
+    assert_eq!(empty, ProteinProperties::default());
+}
@@ -0,0 +1,59 @@
+use crate::lab::regulatory_network::RegulatoryNetwork;
+use crate::lab::types::gene::{Gene, GeneProperties};
+use crate::lab::types::gene_type::{GeneType, GeneTypeTrait};
+use crate::lab::types::regulation::{Regulation, RegulationKind, RegulationTarget};
+
+fn gene(gene_type: GeneType, node_id: u64) -> Gene {
+    Gene::new(Box::new(gene_type), node_id, 1.0, GeneProperties::new())
+}
+
+fn regulator(kind: RegulationKind, target: RegulationTarget, magnitude: f32) -> Gene {
+    let gene_type = match kind {
+        RegulationKind::Promoter => GeneType::Promoter,
+        RegulationKind::Enhancer => GeneType::Enhancer,
+        RegulationKind::Repressor => GeneType::Repressor,
+    };
+    gene(gene_type, 0).with_regulation(Regulation {
+        kind,
+        target,
+        magnitude,
+    })
+}
+
+#[test]
+fn test_unregulated_genes_keep_strength() {
+    let genes = vec![gene(GeneType::Size, 0), gene(GeneType::Color, 1)];
+    let steady_state = RegulatoryNetwork::new(&genes).solve();
+    assert!(steady_state.converged);
+    assert_eq!(steady_state.strengths, vec![1.0, 1.0]);
+}
+
+#[test]
+fn test_enhancer_and_repressor() {
+    let mut genes = vec![
+        gene(GeneType::Size, 0),
+        gene(GeneType::Color, 1),
+        regulator(
+            RegulationKind::Enhancer,
+            RegulationTarget::GeneType(GeneType::Size.id_sequence()),
+            0.5,
+        ),
+        regulator(RegulationKind::Repressor, RegulationTarget::Node(1), 1.0),
+    ];
+
+    let steady_state = RegulatoryNetwork::regulate(&mut genes);
+    assert!(steady_state.converged);
+    assert!((genes[0].get_strength() - 1.5).abs() < 1e-3);
+    assert!(genes[1].get_strength() < 1e-3);
+}
+
+#[test]
+fn test_promoter() {
+    let mut genes = vec![
+        gene(GeneType::Speed, 3),
+        regulator(RegulationKind::Promoter, RegulationTarget::Node(3), 0.25),
+    ];
+
+    RegulatoryNetwork::regulate(&mut genes);
+    assert!((genes[0].get_strength() - 1.25).abs() < 1e-3);
+}
@@ -0,0 +1,105 @@
+use crate::lab::Lab;
+use crate::lab::render::{GenomeView, nucleotides_ansi};
+use crate::lab::types::codon::{Codon, CodonASN, CodonLYS, CodonPRO, CodonSTOP};
+use crate::lab::types::gene_type::{GeneType, GeneTypeTrait};
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide::*;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::tests::get_default_config;
+
+/// A chromosome with a size gene from codon 1 to 7 and two trailing nucleotides.
+fn get_genome() -> Genome {
+    let mut codons = vec![Codon::Trp, Codon::Met];
+    codons.extend(
+        GeneType::Size
+            .id_sequence()
+            .into_iter()
+            .map(Codon::random_from_type),
+    );
+    codons.extend([
+        Codon::Lys(CodonLYS::AAA),
+        Codon::Asn(CodonASN::AAT),
+        Codon::Pro(CodonPRO::CCC),
+        Codon::Stop(CodonSTOP::TAA),
+        Codon::Met,
+    ]);
+    let mut nucleotides: Vec<_> = codons.iter().flat_map(Codon::to_nucleotides).collect();
+    nucleotides.extend([G, C]);
+
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new("1", NucleotideSequence::new(nucleotides)))
+        .unwrap();
+    genome
+}
+
+fn strip_ansi(text: &str) -> String {
+    let mut stripped = String::new();
+    let mut escape = false;
+    for character in text.chars() {
+        match character {
+            '\x1b' => escape = true,
+            'm' if escape => escape = false,
+            _ if escape => {}
+            character => stripped.push(character),
+        }
+    }
+    stripped
+}
+
+#[test]
+fn test_gene_regions() {
+    let lab = Lab::with_builtin_genes(get_default_config());
+    let genome = get_genome();
+    let view = GenomeView::new(&lab, &genome);
+
+    let chromosome = &view.get_chromosomes()[0];
+    let end = 2 + GeneType::Size.id_sequence().len() + 4;
+    assert_eq!(chromosome.get_genes().len(), 1);
+    assert_eq!(chromosome.get_genes()[0].get_codons(), 1..end);
+    assert_eq!(chromosome.gene_at(0), None);
+    assert_eq!(chromosome.gene_at(1), Some(0));
+    assert_eq!(chromosome.gene_at(end), None);
+    assert_eq!(
+        lab.read_genes(chromosome.get_codons().get_sequence()).len(),
+        chromosome.get_genes().len()
+    );
+}
+
+#[test]
+fn test_ansi_rendering() {
+    let lab = Lab::with_builtin_genes(get_default_config());
+    let genome = get_genome();
+    let ansi = GenomeView::new(&lab, &genome).to_ansi();
+    let lines: Vec<String> = ansi.lines().map(strip_ansi).collect();
+
+    let code = genome.get_chromosomes()[0].get_sequence().get_code_string();
+    assert_eq!(lines[0], "1");
+    assert!(lines[1].starts_with(" 0  TGG ATG "));
+    assert_eq!(
+        lines[1].replace(' ', "").trim_start_matches('0'),
+        &code[..code.len() - 2]
+    );
+    assert!(lines[2].starts_with("     W   M "));
+    assert!(lines[2].ends_with("*   M"));
+    assert!(lines[3].ends_with("GC"));
+    assert!(lines[4].starts_with("    size node 1, nucleotides 3-"));
+
+    assert!(ansi.contains("\x1b[1;4m\x1b[32mA\x1b[31mT\x1b[33mG"));
+    assert_eq!(nucleotides_ansi(&[A, C]), "\x1b[32mA\x1b[34mC\x1b[0m");
+}
+
+#[test]
+fn test_html_rendering() {
+    let lab = Lab::with_builtin_genes(get_default_config());
+    let html = GenomeView::new(&lab, &get_genome()).to_html("<genome>");
+
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.contains("<title>&lt;genome&gt;</title>"));
+    assert!(html.contains("class=\"codon gene-"));
+    assert!(html.contains(" start\" title=\"3: Met, size gene\""));
+    assert!(html.contains(" stop\""));
+    assert!(html.contains("<span class=\"remainder\"><span class=\"g\">G</span>"));
+    assert!(html.contains("size node 1, nucleotides 3-"));
+    assert!(html.ends_with("</html>\n"));
+}
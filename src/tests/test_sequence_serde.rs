@@ -0,0 +1,84 @@
+use crate::lab::types::codon::{Codon, CodonLEU, CodonType};
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide::Nucleotide::*;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::sequence_serde;
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SaveFile {
+    #[serde(with = "sequence_serde::code")]
+    code: NucleotideSequence,
+    #[serde(with = "sequence_serde::base64")]
+    packed: NucleotideSequence,
+    #[serde(with = "sequence_serde::readable")]
+    readable: CodonSequence,
+}
+
+fn get_sequence() -> NucleotideSequence {
+    NucleotideSequence::new(vec![A, T, G, G, C, A, T, T])
+}
+
+#[test]
+fn test_codon_and_nucleotide_serde() {
+    let codon = Codon::Leu(CodonLEU::CTG);
+    assert_eq!(serde_json::to_string(&codon).unwrap(), "\"CTG\"");
+    assert_eq!(serde_json::from_str::<Codon>("\"ctg\"").unwrap(), codon);
+    assert!(serde_json::from_str::<Codon>("\"CTGA\"").is_err());
+
+    assert_eq!(serde_json::to_string(&CodonType::Stop).unwrap(), "\"stop\"");
+    assert_eq!(serde_json::to_string(&[G, C]).unwrap(), "[\"G\",\"C\"]");
+    assert!(serde_json::from_str::<crate::lab::types::nucleotide::Nucleotide>("\"U\"").is_err());
+
+    for index in 0..64 {
+        assert_eq!(Codon::from_index(index).unwrap().get_index(), index);
+    }
+    assert_eq!(Codon::from_index(64), None);
+}
+
+#[test]
+fn test_sequence_representations() {
+    let save_file = SaveFile {
+        code: get_sequence(),
+        packed: get_sequence(),
+        readable: CodonSequence::from_nucleotides(get_sequence().get_sequence()),
+    };
+
+    let json = serde_json::to_value(&save_file).unwrap();
+    assert_eq!(json["code"], "ATGGCATT");
+    assert_eq!(json["packed"], "base64:ABrF");
+    assert_eq!(json["readable"], "ATG GCA TT");
+    assert_eq!(serde_json::from_value::<SaveFile>(json).unwrap(), save_file);
+
+    let yaml = serde_yaml::to_string(&save_file).unwrap();
+    assert_eq!(serde_yaml::from_str::<SaveFile>(&yaml).unwrap(), save_file);
+
+    // Every representation is accepted regardless of the selected one.
+    for input in [
+        "\"base64:ABrF\"",
+        "\"atg gca\\ntt\"",
+        "[\"A\",\"T\",\"G\",\"G\",\"C\",\"A\",\"T\",\"T\"]",
+    ] {
+        let sequence: NucleotideSequence = serde_json::from_str(input).unwrap();
+        assert_eq!(sequence, get_sequence());
+    }
+    assert!(serde_json::from_str::<NucleotideSequence>("\"ATGX\"").is_err());
+    assert!(serde_json::from_str::<NucleotideSequence>("\"base64:BA==\"").is_err());
+}
+
+#[test]
+fn test_genome_in_save_file() {
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new("1", get_sequence()))
+        .unwrap();
+
+    let yaml = serde_yaml::to_string(&genome).unwrap();
+    assert!(yaml.contains("ATGGCATT"));
+    assert_eq!(serde_yaml::from_str::<Genome>(&yaml).unwrap(), genome);
+
+    let duplicate = "chromosomes:\n- name: '1'\n  sequence: ATG\n- name: '1'\n  sequence: GGC\n";
+    let error = serde_yaml::from_str::<Genome>(duplicate).unwrap_err();
+    assert!(error.to_string().contains("chromosome '1' already exists"));
+}
@@ -0,0 +1,87 @@
+use crate::lab::Lab;
+use crate::lab::error::{MotifError, SessionError};
+use crate::lab::patch::GenomePatch;
+use crate::lab::session::Session;
+use crate::lab::types::genome::{Chromosome, Genome};
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::tests::get_default_config;
+
+fn get_session() -> Session {
+    let mut session = Session::new(Lab::with_builtin_genes(get_default_config()));
+    let mut genome = Genome::new();
+    genome
+        .add_chromosome(Chromosome::new(
+            "1",
+            NucleotideSequence::from_code_string("ATGGCATAA").unwrap(),
+        ))
+        .unwrap();
+    session.set_genome("g", genome);
+    session
+}
+
+#[test]
+fn test_views() {
+    let mut session = get_session();
+    assert_eq!(session.execute("show g").unwrap(), "1: ATGGCATAA");
+    assert_eq!(session.execute("codons g 1").unwrap(), "1: ATG GCA TAA");
+    assert_eq!(session.execute("protein g").unwrap(), "1: MA*");
+    assert_eq!(
+        session.execute("bits g").unwrap(),
+        format!(
+            "1: {}",
+            session.get_genome("g").unwrap().get_chromosomes()[0]
+                .get_sequence()
+                .get_bit_string()
+        )
+    );
+    assert_eq!(session.execute("find g TRA").unwrap(), "1: 6..9 +");
+    assert_eq!(session.execute("  ").unwrap(), "");
+}
+
+#[test]
+fn test_modifying_commands() {
+    let mut session = get_session();
+    session.execute("copy g h").unwrap();
+    assert_eq!(session.execute("mutate h 1").unwrap(), "9 substitutions");
+    let diff = session.execute("diff g h").unwrap();
+    assert!(diff.starts_with("bio-lab patch 1\n@ 1\n"));
+    let patched = GenomePatch::from_text(&diff)
+        .unwrap()
+        .apply(session.get_genome("g").unwrap())
+        .unwrap();
+    assert_eq!(Some(&patched), session.get_genome("h"));
+
+    session.execute("random r 2 30").unwrap();
+    assert_eq!(session.get_genome("r").unwrap().get_chromosomes().len(), 2);
+    session.execute("drop r").unwrap();
+    assert!(session.get_genome("r").is_none());
+}
+
+#[test]
+fn test_session_errors() {
+    let mut session = get_session();
+    assert!(matches!(
+        session.execute("frobnicate"),
+        Err(SessionError::UnknownCommand(_))
+    ));
+    assert!(matches!(
+        session.execute("show"),
+        Err(SessionError::Usage(usage)) if usage.starts_with("show <name>")
+    ));
+    assert!(matches!(
+        session.execute("show missing"),
+        Err(SessionError::UnknownGenome(_))
+    ));
+    assert!(matches!(
+        session.execute("show g 7"),
+        Err(SessionError::UnknownChromosome(_))
+    ));
+    assert!(matches!(
+        session.execute("random r many"),
+        Err(SessionError::InvalidArgument(_))
+    ));
+    assert!(matches!(
+        session.execute("find g TA["),
+        Err(SessionError::Motif(MotifError::Parse { position: 2, .. }))
+    ));
+}
@@ -0,0 +1,91 @@
+use crate::lab::Lab;
+use crate::lab::config::config_format::ConfigFormat;
+use crate::lab::error::SnapshotError;
+use crate::lab::snapshot::GeneTypeRegistry;
+use crate::lab::types::codon::CodonType;
+use crate::lab::types::dominance::Dominance;
+use crate::lab::types::gene_type::GeneTypeTrait;
+use crate::lab::types::organism::Organism;
+use crate::tests::{TestGeneType, get_default_config};
+
+fn restore_test(data: &serde_yaml::Value) -> Result<Box<dyn GeneTypeTrait>, String> {
+    let id = serde_yaml::from_value(data.clone()).map_err(|error| error.to_string())?;
+    Ok(Box::new(TestGeneType(id)))
+}
+
+fn get_registry() -> GeneTypeRegistry {
+    let mut registry = GeneTypeRegistry::with_builtin();
+    registry.register("test", restore_test);
+    registry
+}
+
+fn get_lab() -> Lab {
+    let mut config = get_default_config();
+    config
+        .dominance
+        .insert("color".to_string(), Dominance::Recessive);
+
+    let mut lab = Lab::with_builtin_genes(config);
+    lab.register_gene(Box::new(TestGeneType(vec![CodonType::Met, CodonType::Trp])))
+        .unwrap();
+    lab.set_population(
+        "founders",
+        vec![Organism::random(2, 60), Organism::random(2, 60)],
+    );
+    lab.set_population("empty", vec![]);
+    lab
+}
+
+#[test]
+fn test_snapshot_roundtrip() {
+    let lab = get_lab();
+    let snapshot = lab.snapshot().unwrap();
+    let restored = Lab::from_snapshot(snapshot.clone(), &get_registry()).unwrap();
+
+    assert_eq!(restored.snapshot().unwrap(), snapshot);
+    assert_eq!(restored.get_config(), lab.get_config());
+    assert_eq!(restored.gene_types().count(), lab.gene_types().count());
+    assert_eq!(
+        restored
+            .get_gene_type(&[CodonType::Met, CodonType::Trp])
+            .map(|gene_type| gene_type.name()),
+        Some("test")
+    );
+    assert_eq!(restored.get_populations(), lab.get_populations());
+}
+
+#[test]
+fn test_save_and_load_snapshot() {
+    let lab = get_lab();
+    let dir = std::env::temp_dir().join(format!("bio_lab_snapshot_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+
+    for format in ConfigFormat::all() {
+        let path = dir.join(format!("lab.{}", format.get_extension()));
+        lab.save(&path).unwrap();
+        let restored = Lab::load(&path, &get_registry()).unwrap();
+        assert_eq!(restored.snapshot().unwrap(), lab.snapshot().unwrap());
+    }
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn test_snapshot_errors() {
+    let lab = get_lab();
+    let snapshot = lab.snapshot().unwrap();
+
+    let error =
+        Lab::from_snapshot(snapshot.clone(), &GeneTypeRegistry::with_builtin()).unwrap_err();
+    assert!(matches!(error, SnapshotError::UnknownGeneTypeTag(tag) if tag == "test"));
+
+    let mut outdated = snapshot.clone();
+    outdated.version += 1;
+    let error = Lab::from_snapshot(outdated, &get_registry()).unwrap_err();
+    assert!(matches!(error, SnapshotError::UnsupportedVersion(_)));
+
+    let mut invalid = snapshot;
+    invalid.gene_types[0].data = serde_yaml::Value::String("unknown".to_string());
+    let error = Lab::from_snapshot(invalid, &get_registry()).unwrap_err();
+    assert!(matches!(error, SnapshotError::InvalidGeneType { .. }));
+}
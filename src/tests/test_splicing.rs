@@ -0,0 +1,68 @@
+use crate::lab::types::codon_sequence::CodonSequence;
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::splicing::SpliceSites;
+
+fn get_sequence() -> NucleotideSequence {
+    NucleotideSequence::from_code_string("ATGCCCGTAAGTGGGTTTCAGAAAGTAAGTTTTCAGTTT").unwrap()
+}
+
+#[test]
+fn test_splice() {
+    let splice_sites = SpliceSites::default();
+    let sequence = get_sequence();
+
+    assert_eq!(
+        splice_sites.find_introns(sequence.get_sequence()),
+        vec![6..21, 24..36]
+    );
+    assert_eq!(
+        splice_sites.splice(&sequence).get_code_string(),
+        "ATGCCCAAATTT"
+    );
+
+    let codons = CodonSequence::from_spliced_nucleotides(sequence.get_sequence(), &splice_sites);
+    assert_eq!(codons.get_code_string(), "ATGCCCAAATTT");
+}
+
+#[test]
+fn test_unterminated_intron_is_kept() {
+    let splice_sites = SpliceSites::default();
+    let sequence = NucleotideSequence::from_code_string("ATGGTAAGTCCC").unwrap();
+    assert_eq!(splice_sites.splice(&sequence), sequence);
+}
+
+#[test]
+fn test_alternative_transcripts() {
+    let splice_sites = SpliceSites::default();
+    let transcripts: Vec<String> = splice_sites
+        .alternative_transcripts(&get_sequence(), 10)
+        .iter()
+        .map(|transcript| transcript.get_code_string())
+        .collect();
+    assert_eq!(transcripts, vec!["ATGCCCAAATTT", "ATGCCCTTT"]);
+
+    assert_eq!(
+        splice_sites
+            .alternative_transcripts(&get_sequence(), 1)
+            .len(),
+        1
+    );
+}
+
+#[test]
+fn test_alternative_transcripts_with_many_introns() {
+    let splice_sites = SpliceSites::default();
+    let sequence =
+        NucleotideSequence::from_code_string(&format!("A{}", "GTAAGTTTTCAGC".repeat(70))).unwrap();
+
+    let transcripts = splice_sites.alternative_transcripts(&sequence, 3);
+    assert_eq!(transcripts.len(), 3);
+    assert_eq!(
+        transcripts[0].get_code_string(),
+        format!("A{}", "C".repeat(70))
+    );
+    assert_eq!(
+        transcripts[1].get_code_string(),
+        format!("A{}", "C".repeat(69))
+    );
+}
@@ -0,0 +1,52 @@
+use crate::lab::Lab;
+use crate::lab::config::transcription_config::TranscriptionConfig;
+use crate::lab::types::codon::{Codon, CodonLYS};
+use crate::lab::types::nucleotide_sequence::NucleotideSequence;
+use crate::lab::types::rna_sequence::RnaSequence;
+use crate::lab::types::transcription::TranscriptionMotifs;
+use crate::tests::get_default_config;
+
+#[test]
+fn test_rna_code_string() {
+    let sequence = NucleotideSequence::from_code_string("ATGTTA").unwrap();
+    let rna = RnaSequence::from(&sequence);
+    assert_eq!(rna.get_code_string(), "AUGUUA");
+    assert_eq!(RnaSequence::from_code_string("AUGUUA").unwrap(), rna);
+    assert!(RnaSequence::from_code_string("ATG").is_err());
+    assert_eq!(rna.translate().get_code_string(), "ATGTTA");
+}
+
+#[test]
+fn test_transcribe() {
+    let motifs = TranscriptionMotifs::default();
+    let sequence = NucleotideSequence::from_code_string("GGTATAATATGAAATTTTTTCCTATAATGGG").unwrap();
+
+    let transcripts: Vec<String> = sequence
+        .transcribe(&motifs)
+        .iter()
+        .map(|rna| rna.get_code_string())
+        .collect();
+    assert_eq!(transcripts, vec!["AUGAAA", "GGG"]);
+
+    let codons = sequence.transcribe(&motifs)[0].translate();
+    assert_eq!(
+        codons.get_sequence(),
+        &[Codon::Met, Codon::Lys(CodonLYS::AAA)]
+    );
+}
+
+#[test]
+fn test_lab_transcribe() {
+    let sequence = NucleotideSequence::from_code_string("CCCTATAATAAA").unwrap();
+
+    let lab = Lab::new(get_default_config());
+    assert_eq!(lab.transcribe(&sequence).len(), 1);
+    assert_eq!(lab.transcribe(&sequence)[0].len(), 12);
+
+    let mut config = get_default_config();
+    config.transcription = Some(TranscriptionConfig::default());
+    let lab = Lab::new(config);
+    let transcripts = lab.transcribe(&sequence);
+    assert_eq!(transcripts.len(), 1);
+    assert_eq!(transcripts[0].get_code_string(), "AAA");
+}